@@ -21,9 +21,12 @@ macro_rules! impl_some_lexer_test {
         $(,)?  // as many as paramerts passed
     ) => {
         $(
-            for token in Lexer::<$lexer>::new($txt) {
-                // debug!("{:?}", token);
-                debug!("{}", token.to_string());
+            for result in Lexer::<$lexer>::new($txt) {
+                match result {
+                    // Ok(token) => debug!("{:?}", token),
+                    Ok(token) => debug!("{} @ {}", token.to_string(), token.span),
+                    Err(error) => error!("{}", error),
+                }
             }
         )+
     };
@@ -1,5 +1,7 @@
 #![allow(unused)]
 
+use dev_utils::{app_dt, dlog::*};
+
 fn main() {
     app_dt!(file!());
     set_max_level(Level::Debug);
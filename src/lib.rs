@@ -29,31 +29,1639 @@ pub use tracer::*;
 
 #[cfg(test)] // Only compiles when running tests
 mod tests {
+    use crate::{ast::*, lexer::*, parser::*, tokens::*};
+
+    /// Renders a node as an s-expression of its tokens (e.g. `(And A B)`).
+    fn sexpr(node: &Node) -> String {
+        let token = format!("{:?}", node.token);
+        let token = match token.split_once("{ value: ") {
+            Some((_, value)) => value.split(',').next().unwrap_or_default().to_string(),
+            None => token,
+        };
+        match node.is_leaf() {
+            true => token,
+            false => {
+                let children: Vec<String> = node.children.iter().map(sexpr).collect();
+                format!("({} {})", token, children.join(" "))
+            }
+        }
+    }
+
+    fn parse_tokens(tokens: Vec<Spanned<Box<dyn Token>>>) -> Result<String, ParseError> {
+        Parser::new(tokens)
+            .parse()
+            .map(|ast| ast.root.as_ref().map(sexpr).unwrap_or_default())
+    }
+
+    fn parse<T: TokenRecognizer + Default>(src: &str) -> Result<String, ParseError> {
+        parse_tokens(Lexer::<T>::new(src).get_token_table().unwrap())
+    }
+
+    #[test]
+    fn test_parse_logic() {
+        [
+            ("A", "A"),
+            ("A & B | C", "(Or (And A B) C)"),
+            ("A | B & C", "(Or A (And B C))"),
+            ("A & B & C", "(And (And A B) C)"),
+            ("!A & B", "(And (Not A) B)"),
+            ("!!A", "(Not (Not A))"),
+            ("A ⊻ B | C ∧ D", "(Or (XOr A B) (And C D))"),
+            ("", ""),
+        ]
+        .iter()
+        .for_each(|(src, tree)| assert_eq!(parse::<LogicLexer>(src).unwrap(), *tree));
+    }
+
+    #[test]
+    fn test_parse_math() {
+        [
+            ("2 - 3 - 4", "(Subtract (Subtract 2.0 3.0) 4.0)"),
+            ("25.1 * 42 - 13", "(Subtract (Multiply 25.1 42.0) 13.0)"),
+            ("1 + 2 * 3", "(Add 1.0 (Multiply 2.0 3.0))"),
+            ("2 ^ 3 ^ 2", "(Power 2.0 (Power 3.0 2.0))"),
+            ("-2 ^ 2", "(Subtract (Power 2.0 2.0))"),
+            ("3! * 2", "(Multiply (Factorial 3.0) 2.0)"),
+            ("√4 + 1", "(Add (Root 4.0) 1.0)"),
+        ]
+        .iter()
+        .for_each(|(src, tree)| assert_eq!(parse::<MathLexer>(src).unwrap(), *tree));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse::<LogicLexer>("A &"), Err(ParseError::UnexpectedEnd));
+        assert!(matches!(
+            parse::<LogicLexer>("A & & B"),
+            Err(ParseError::UnexpectedToken { index: 2, .. })
+        ));
+        assert!(matches!(
+            parse::<LogicLexer>("A B"),
+            Err(ParseError::UnexpectedToken { index: 1, .. })
+        ));
+        assert!(matches!(
+            parse::<MathLexer>("* 2"),
+            Err(ParseError::UnexpectedToken { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_lexer_spans() {
+        let src = "A &  B\n | ¬C";
+        let spans: Vec<(&str, usize, usize)> = Lexer::<LogicLexer>::new(src)
+            .tokenize()
+            .unwrap()
+            .iter()
+            .map(|token| (&src[token.span.range()], token.span.line, token.span.column))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("A", 1, 1),
+                ("&", 1, 3),
+                ("B", 1, 6),
+                ("|", 2, 2),
+                ("¬", 2, 4),
+                ("C", 2, 5),
+            ]
+        );
+
+        let tokens = Lexer::<MathLexer>::new("12.5 *  3.25")
+            .get_token_table()
+            .unwrap();
+        let ranges: Vec<_> = tokens.iter().map(|token| token.span.range()).collect();
+        assert_eq!(ranges, vec![0..4, 5..6, 8..12]);
+    }
+
+    #[test]
+    fn test_lex_errors() {
+        let errors = |src: &str| -> Vec<(LexErrorKind, String, std::ops::Range<usize>)> {
+            match Lexer::<CompleteLexer>::new(src).tokenize() {
+                Ok(_) => vec![],
+                Err(errors) => errors
+                    .into_iter()
+                    .map(|e| (e.kind, e.lexeme, e.span.range()))
+                    .collect(),
+            }
+        };
+        use LexErrorKind::*;
+        assert_eq!(errors("A & B"), vec![]);
+        assert_eq!(errors("A $ B"), vec![(UnknownSymbol, "$".into(), 2..3)]);
+        assert_eq!(
+            errors("1.2.3 + x"),
+            vec![(InvalidNumber, "1.2.3".into(), 0..5)]
+        );
+        assert_eq!(
+            errors("x + \\"),
+            vec![(UnterminatedCommand, "\\".into(), 4..5)]
+        );
+        assert_eq!(
+            errors("\\foo * 2"),
+            vec![(UnknownSymbol, "\\foo".into(), 0..4)]
+        );
+        assert_eq!(
+            errors("§ x # y"),
+            vec![
+                (UnknownSymbol, "§".into(), 0..2),
+                (UnknownSymbol, "#".into(), 5..6)
+            ]
+        );
+        // * numbers not accepted by the lexer are invalid
+        let errors = Lexer::<LogicLexer>::new("A & 1.5").tokenize().unwrap_err();
+        assert_eq!(errors[0].kind, InvalidNumber);
+    }
+
+    #[test]
+    fn test_lex_recovery() {
+        let (tokens, errors) = Lexer::<LogicLexer>::new("A & $ B ? | C").tokenize_recovering();
+        assert_eq!(tokens.len(), 5);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            format!("{}", errors[1]),
+            "unknown symbol `?` at 1:9".to_string()
+        );
+        // * the token table doesn't leave out what can't be lexed, recovering does
+        let errors = Lexer::<LogicLexer>::new("A & $ B")
+            .get_token_table()
+            .unwrap_err();
+        assert_eq!(errors[0].span.range(), 4..5);
+        let (tokens, _) = Lexer::<LogicLexer>::new("A & $ B").tokenize_recovering();
+        assert_eq!(parse_tokens(tokens), Ok("(And A B)".to_string()));
+    }
+
+    #[test]
+    fn test_lexer_longest_match() {
+        crate::impl_lexer_token_from!(RelLexer; RelationalOp, AlphaUpper);
+
+        let lexemes = |src: &'static str| -> Vec<&'static str> {
+            Lexer::<RelLexer>::new(src)
+                .tokenize()
+                .unwrap()
+                .iter()
+                .map(|token| &src[token.span.range()])
+                .collect()
+        };
+        assert_eq!(lexemes("A <= B"), vec!["A", "<=", "B"]);
+        assert_eq!(lexemes("A<B>=C"), vec!["A", "<", "B", ">=", "C"]);
+        assert_eq!(lexemes("A == B != C"), vec!["A", "==", "B", "!=", "C"]);
+        assert_eq!(lexemes("A=B"), vec!["A", "=", "B"]);
+    }
+
+    #[test]
+    fn test_every_symbol_is_lexable() {
+        fn check<T: Token>() {
+            // * `<-` is `<` and `-` where comparisons are read (see test_comparisons)
+            for symbol in T::symbols().iter().filter(|&&symbol| symbol != "<-") {
+                let tokens = Lexer::<CompleteLexer>::new(symbol).tokenize();
+                let tokens = tokens.unwrap_or_else(|e| panic!("{symbol:?} can't be lexed: {e:?}"));
+                assert_eq!(tokens.len(), 1, "{symbol:?} is lexed as {tokens:?}");
+                assert_eq!(tokens[0].span.range(), 0..symbol.len());
+            }
+        }
+        check::<MathOp>();
+        check::<LogicOp>();
+        check::<Alphabet>();
+        check::<AlphaUpper>();
+        check::<GreekAlpha>();
+        check::<GreekUpperAlpha>();
+        check::<MathConst>();
+        check::<PhysicConst>();
+        check::<Bracket>();
+        check::<RelationalOp>();
+        check::<BitOp>();
+        check::<BoolConst>();
+    }
+
+    #[test]
+    fn test_dialects() {
+        use crate::dialect::Dialect::{self, *};
+
+        fn lex<T: TokenRecognizer + Default>(src: &str, dialect: Dialect) -> Vec<String> {
+            let (tokens, errors) = Lexer::<T>::new(src)
+                .with_dialect(dialect)
+                .tokenize_recovering();
+            let mut tokens: Vec<String> = tokens.iter().map(|t| format!("{:?}", t.value)).collect();
+            tokens.extend(errors.iter().map(|e| format!("<{}>", e.lexeme)));
+            tokens
+        }
+        [
+            ("A ^ B", Textbook, vec!["A", "And", "B"]),
+            ("A ^ B", Programmer, vec!["A", "XOr", "B"]),
+            ("A ^ B", Electronics, vec!["A", "XOr", "B"]),
+            ("A * B + C", Textbook, vec!["A", "And", "B", "Or", "C"]),
+            ("A * B + C", Electronics, vec!["A", "And", "B", "Or", "C"]),
+            ("A * B + C", Programmer, vec!["A", "B", "C", "<*>", "<+>"]),
+            (
+                "A & B | !C",
+                Programmer,
+                vec!["A", "And", "B", "Or", "Not", "C"],
+            ),
+        ]
+        .iter()
+        .for_each(|(src, dialect, tokens)| {
+            assert_eq!(
+                lex::<LogicLexer>(src, *dialect),
+                *tokens,
+                "{src} ({dialect:?})"
+            )
+        });
+
+        // * constants
+        assert_eq!(
+            lex::<CompleteLexer>("e π x", Textbook),
+            vec!["E", "Pi", "X"]
+        );
+        assert_eq!(lex::<CompleteLexer>("e", Programmer), vec!["E"]);
+        let tokens = Lexer::<CompleteLexer>::new("e π")
+            .with_dialect(Programmer)
+            .get_token_table()
+            .unwrap();
+        assert!(tokens[0].downcast_ref::<Alphabet>().is_some());
+        assert!(tokens[1].downcast_ref::<GreekAlpha>().is_some());
+        let tokens = Lexer::<CompleteLexer>::new("e π")
+            .with_dialect(Textbook)
+            .get_token_table()
+            .unwrap();
+        assert!(tokens[0].downcast_ref::<MathConst>().is_some());
+        assert!(tokens[1].downcast_ref::<MathConst>().is_some());
+        // * `+` is still an arithmetic operator when the lexer knows it
+        assert_eq!(
+            lex::<CompleteLexer>("1 + 2", Programmer),
+            vec![
+                "Natural { value: 1, base: 10 }",
+                "Add",
+                "Natural { value: 2, base: 10 }"
+            ]
+        );
+
+        // * precedence
+        let parse = |src: &str, dialect: Dialect| {
+            let tokens = Lexer::<LogicLexer>::new(src)
+                .with_dialect(dialect)
+                .get_token_table()
+                .unwrap();
+            Parser::new(tokens)
+                .with_dialect(dialect)
+                .parse()
+                .map(|ast| sexpr(ast.root.as_ref().unwrap()))
+        };
+        assert_eq!(parse("A | B ⊕ C", Textbook).unwrap(), "(XOr (Or A B) C)");
+        assert_eq!(parse("A | B ⊕ C", Programmer).unwrap(), "(Or A (XOr B C))");
+        assert_eq!(
+            parse("A ^ B & C", Electronics).unwrap(),
+            "(XOr A (And B C))"
+        );
+        assert_eq!(parse("A ^ B | C", Textbook).unwrap(), "(Or (And A B) C)");
+    }
+
+    #[test]
+    fn test_token_registry() {
+        use crate::registry::*;
+
+        let lex = |src: &str, registry: &TokenRegistry| -> Vec<String> {
+            let (tokens, errors) = Lexer::with_recognizer(src, registry).tokenize_recovering();
+            let mut tokens: Vec<String> = tokens.iter().map(|t| format!("{:?}", t.value)).collect();
+            tokens.extend(errors.iter().map(|e| format!("<{}>", e.lexeme)));
+            tokens
+        };
+
+        let mut registry = TokenRegistry::new().with::<LogicOp>().with::<AlphaUpper>();
+        assert_eq!(lex("A & B", &registry), vec!["A", "And", "B"]);
+        assert_eq!(lex("A ∧ b", &registry), vec!["A", "And", "<b>"]);
+
+        // * aliases can be added and removed at runtime
+        registry.add_alias("ET", "&").unwrap();
+        registry.add_alias("∩", "ET").unwrap(); // alias of an alias
+        assert_eq!(
+            lex("A ET B ∩ C", &registry),
+            vec!["A", "And", "B", "And", "C"]
+        );
+        assert_eq!(registry.remove_alias("ET"), Some("&".to_string()));
+        assert_eq!(lex("A ET B", &registry), vec!["A", "E", "T", "B"]);
+        assert_eq!(
+            registry.add_alias("XX", "??"),
+            Err(RegistryError::UnknownSymbol("??".to_string()))
+        );
+
+        // * categories too
+        registry.register::<Alphabet>();
+        assert_eq!(lex("A ∧ b", &registry), vec!["A", "And", "B"]);
+        assert!(registry.unregister::<AlphaUpper>());
+        assert!(!registry.unregister::<AlphaUpper>());
+        assert_eq!(lex("A ∧ b", &registry), vec!["And", "B", "<A>"]);
+
+        // * aliases from a config file
+        let mut registry = TokenRegistry::new().with::<LogicOp>().with::<AlphaUpper>();
+        let config = "# logic\nET = &\n\nOU = |\n/\\ = ∧\n";
+        assert_eq!(registry.load_aliases(config), Ok(3));
+        assert_eq!(
+            lex("A /\\ B OU C", &registry),
+            vec!["A", "And", "B", "Or", "C"]
+        );
+        assert_eq!(
+            registry.load_aliases("NON = !\nXOU ^"),
+            Err(RegistryError::InvalidLine {
+                line: 2,
+                text: "XOU ^".to_string()
+            })
+        );
+        assert_eq!(lex("NON A", &registry), vec!["N", "O", "N", "A"]); // nothing was added
+    }
+
+    #[test]
+    fn test_identifiers() {
+        let identifiers = |mut lexer: Lexer<'_, CompleteLexer>| -> Vec<(String, Option<String>)> {
+            lexer
+                .get_token_table()
+                .unwrap()
+                .iter()
+                .filter_map(|token| token.downcast_ref::<Identifier>())
+                .map(|id| (id.name().to_string(), id.subscript().map(str::to_string)))
+                .collect()
+        };
+        let id =
+            |name: &str, subscript: Option<&str>| (name.to_string(), subscript.map(str::to_string));
+
+        let src = "Q_2 & x_10 | A_12 * θ_0 + π_1 - AB";
+        assert_eq!(
+            identifiers(Lexer::new(src)),
+            vec![
+                id("Q", Some("2")),
+                id("x", Some("10")),
+                id("A", Some("12")),
+                id("θ", Some("0")),
+                id("π", Some("1"))
+            ]
+        );
+        assert_eq!(
+            Lexer::<CompleteLexer>::new(src)
+                .get_token_table()
+                .unwrap()
+                .len(),
+            12
+        );
+
+        let src = "enable & carry_in | αβ_2 + x1 - π";
+        assert_eq!(
+            identifiers(Lexer::new(src).with_multi_letter_names(true)),
+            vec![
+                id("enable", None),
+                id("carry_in", None),
+                id("αβ", Some("2")),
+                id("x1", None),
+            ]
+        );
+        // * a bare constant is never an identifier
+        let tokens = Lexer::<CompleteLexer>::new("π")
+            .with_multi_letter_names(true)
+            .get_token_table()
+            .unwrap();
+        assert!(tokens[0].downcast_ref::<MathConst>().is_some());
+
+        assert!(Identifier::from_str("x_").is_none());
+        assert!(Identifier::from_str("1x").is_none());
+        assert!(Identifier::from_str("τ").is_none());
+        assert_eq!(
+            Identifier::from_str("carry_in_3").unwrap().symbol(),
+            "carry_in_3"
+        );
+        // * the subscript is kept as it's written
+        let x_01 = Identifier::from_str("x_01").unwrap();
+        assert_eq!(
+            (x_01.subscript(), x_01.symbol().as_str()),
+            (Some("01"), "x_01")
+        );
+        assert_ne!(x_01, Identifier::from_str("x_1").unwrap());
+
+        let mut lexer =
+            Lexer::<LogicLexer>::new("!enable & carry_in").with_multi_letter_names(true);
+        assert!(
+            Parser::new(lexer.get_token_table().unwrap())
+                .parse()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_numbers_in_any_base() {
+        let numbers = |src: &str| -> Vec<(usize, u8)> {
+            Lexer::<CompleteLexer>::new(src)
+                .tokenize()
+                .unwrap()
+                .iter()
+                .map(|token| token.downcast_ref::<Natural>().unwrap())
+                .map(|n| (n.value(), n.base()))
+                .collect()
+        };
+        assert_eq!(
+            numbers("0b1011 0o17 0xFF 0XfF 16#1F 2#101 36#z 0 42"),
+            vec![
+                (11, 2),
+                (15, 8),
+                (255, 16),
+                (255, 16),
+                (31, 16),
+                (5, 2),
+                (35, 36),
+                (0, 10),
+                (42, 10)
+            ]
+        );
+
+        let errors = |src: &str| -> Vec<String> {
+            match Lexer::<LogicLexer>::new(src).tokenize() {
+                Ok(_) => vec![],
+                Err(errors) => errors.into_iter().map(|e| e.lexeme).collect(),
+            }
+        };
+        assert_eq!(
+            errors("0b102 & 0xFG | 37#1 & 1#0"),
+            vec!["0b102", "0xFG", "37#1", "1#0"]
+        );
+        // * a prefix without digits is just a `0` followed by a letter
+        assert_eq!(
+            Lexer::<LogicLexer>::new("0b & 0x")
+                .tokenize()
+                .unwrap()
+                .len(),
+            5
+        );
+
+        let parsed = |src: &str| Integer::from_str(src).map(|n| (n.value(), n.base()));
+        assert_eq!(parsed("-0x1F"), Some((-31, 16)));
+        assert_eq!(parsed("-12"), Some((-12, 10)));
+        assert_eq!(Natural::from_str("-0x1F"), None);
+        assert_eq!(Natural::from_str("0xFFFFFFFFFFFFFFFFFF"), None); // overflow
+        assert_eq!(Real::from_str("0x1F"), None);
+        assert_eq!(Real::from_str("inf"), None);
+        assert_eq!(Real::from_str("2.5").map(|n| n.value()), Some(2.5));
+
+        assert_eq!(
+            parse::<MathLexer>("0xFF * 2#10").unwrap(),
+            "(Multiply 255 2)".to_string()
+        );
+    }
+
+    #[test]
+    fn test_implication() {
+        use crate::eval::*;
+
+        [
+            ("A -> B -> C", "(Implies A (Implies B C))"),
+            ("A → B ∨ C", "(Implies A (Or B C))"),
+            ("A <-> B => C", "(Iff A (Implies B C))"),
+            ("A \\iff B", "(Iff A B)"),
+            ("A ⇐ B <- C", "(ConverseImplies (ConverseImplies A B) C)"),
+            ("A ↛ B & C", "(NotImplies A (And B C))"),
+            ("¬A ⇔ B", "(Iff (Not A) B)"),
+        ]
+        .iter()
+        .for_each(|(src, sexpr)| assert_eq!(parse::<LogicLexer>(src).unwrap(), *sexpr, "{src}"));
+
+        let eval = |src: &str, a: bool, b: bool| {
+            let tokens = Lexer::<LogicLexer>::new(src).get_token_table().unwrap();
+            let env = Environment::new().with("A", a).with("B", b);
+            Parser::new(tokens).parse().unwrap().evaluate(&env)
+        };
+        // * truth tables for (A, B) = (F, F), (F, T), (T, F), (T, T)
+        [
+            ("A -> B", [true, true, false, true]),
+            ("A <- B", [true, false, true, true]),
+            ("A <-> B", [true, false, false, true]),
+            ("A -/> B", [false, false, true, false]),
+            ("A -> B -> A", [true, true, true, true]),
+        ]
+        .iter()
+        .for_each(|(src, table)| {
+            let values: Vec<Value> = [(false, false), (false, true), (true, false), (true, true)]
+                .iter()
+                .map(|(a, b)| eval(src, *a, *b).unwrap())
+                .collect();
+            assert_eq!(values, table.map(Value::Bool), "{src}");
+        });
+
+        assert_eq!(
+            eval("A -> C", true, true),
+            Err(EvalError::UnboundVariable("C".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_word_operators() {
+        let lex = |src: &str, multi_letter_names: bool| -> Vec<String> {
+            Lexer::<LogicLexer>::new(src)
+                .with_multi_letter_names(multi_letter_names)
+                .get_token_table()
+                .unwrap()
+                .iter()
+                .map(|t| format!("{:?}", t.value))
+                .collect()
+        };
+        [
+            ("a and b", vec!["A", "And", "B"]),
+            ("A AND NOT B Or C", vec!["A", "And", "Not", "B", "Or", "C"]),
+            (
+                "(A xor B) nand C",
+                vec!["OpenParen", "A", "XOr", "B", "CloseParen", "NAnd", "C"],
+            ),
+            ("A implies B iff C", vec!["A", "Implies", "B", "Iff", "C"]),
+            ("A xnor B nor C", vec!["A", "XNOr", "B", "NOr", "C"]),
+            ("band", vec!["B", "A", "N", "D"]), // not a whole word
+            (
+                "nor2",
+                vec!["N", "O", "R", "Natural { value: 2, base: 10 }"],
+            ),
+            (
+                "or_1",
+                vec!["O", "Identifier { name: \"r\", subscript: Some(\"1\") }"],
+            ),
+        ]
+        .iter()
+        .for_each(|(src, tokens)| assert_eq!(lex(src, false), *tokens, "{src}"));
+        [
+            ("a and b", vec!["A", "And", "B"]),
+            (
+                "android or not_x",
+                vec![
+                    "Identifier { name: \"android\", subscript: None }",
+                    "Or",
+                    "Identifier { name: \"not_x\", subscript: None }",
+                ],
+            ),
+        ]
+        .iter()
+        .for_each(|(src, tokens)| assert_eq!(lex(src, true), *tokens, "{src}"));
+
+        assert_eq!(
+            parse::<LogicLexer>("A and not B or C").unwrap(),
+            parse::<LogicLexer>("A & !B | C").unwrap()
+        );
+        assert_eq!(
+            parse::<MathLexer>("7 MOD 4 * 2").unwrap(),
+            "(Multiply (Modulo 7.0 4.0) 2.0)".to_string()
+        );
+    }
+
+    #[test]
+    fn test_latex_commands() {
+        [
+            ("\\neg p \\land q \\to r", "¬p ∧ q → r"),
+            ("A \\lor B \\leftrightarrow \\lnot C", "A ∨ B ↔ ¬C"),
+            ("A \\oplus B \\rightarrow A \\wedge B", "A ⊕ B → A ∧ B"),
+            ("\\langle A \\vee B \\rangle", "A | B"),
+        ]
+        .iter()
+        .for_each(|(latex, unicode)| {
+            assert_eq!(
+                parse::<LogicLexer>(latex).unwrap(),
+                parse::<LogicLexer>(unicode).unwrap(),
+                "{latex}"
+            )
+        });
+        assert_eq!(
+            parse::<MathLexer>("2 \\cdot 3 \\times 4 \\div \\sqrt 2").unwrap(),
+            parse::<MathLexer>("2 * 3 * 4 / √2").unwrap()
+        );
+
+        let lex = |src: &str| -> Vec<String> {
+            let tokens = Lexer::<CompleteLexer>::new(src).tokenize().unwrap();
+            tokens.iter().map(|t| format!("{:?}", t.value)).collect()
+        };
+        [
+            (
+                "\\alpha \\leq \\Omega",
+                vec!["Alpha", "LessThanOrEqual", "Omega"],
+            ),
+            (
+                "x \\geq y \\neq z",
+                vec!["X", "GreaterThanOrEqual", "Y", "NotEqual", "Z"],
+            ),
+            ("\\pi \\varphi \\infty", vec!["Pi", "Phi", "Infinity"]),
+            ("\\top \\lor \\bot", vec!["True", "Or", "False"]),
+        ]
+        .iter()
+        .for_each(|(src, tokens)| assert_eq!(lex(src), *tokens, "{src}"));
+
+        let tokens = Lexer::<LogicLexer>::new("\\top \\to \\bot")
+            .get_token_table()
+            .unwrap();
+        let ast = Parser::new(tokens).parse().unwrap();
+        let value = ast.evaluate(&crate::eval::Environment::new());
+        assert_eq!(value, Ok(crate::eval::Value::Bool(false)));
+    }
+
+    #[test]
+    fn test_bool_constants() {
+        use crate::eval::*;
+
+        let lex = |mut lexer: Lexer<'_, LogicLexer>| -> Vec<String> {
+            let tokens = lexer.get_token_table().unwrap();
+            tokens.iter().map(|t| format!("{:?}", t.value)).collect()
+        };
+        [
+            ("true | FALSE", vec!["True", "Or", "False"]),
+            ("⊤ & ⊥", vec!["True", "And", "False"]),
+            ("A & 1 | 0", vec!["A", "And", "True", "Or", "False"]),
+            ("10", vec!["Natural { value: 10, base: 10 }"]),
+            ("T & F", vec!["T", "And", "F"]),
+        ]
+        .iter()
+        .for_each(|(src, tokens)| assert_eq!(lex(Lexer::new(src)), *tokens, "{src}"));
+        assert_eq!(
+            lex(Lexer::new("T & F | T_1").with_bool_letters(true)),
+            vec![
+                "True",
+                "And",
+                "False",
+                "Or",
+                "Identifier { name: \"T\", subscript: Some(\"1\") }"
+            ]
+        );
+        // * numbers stay numbers in math
+        assert_eq!(parse::<CompleteLexer>("1 + 0").unwrap(), "(Add 1 0)");
+
+        // * identities can be checked for every value of `A`
+        let eval = |src: &str, a: bool| {
+            let tokens = Lexer::<LogicLexer>::new(src).get_token_table().unwrap();
+            let env = Environment::new().with("A", a);
+            Parser::new(tokens).parse().unwrap().evaluate(&env).unwrap()
+        };
+        for a in [false, true] {
+            assert_eq!(eval("A & 1", a), Value::Bool(a));
+            assert_eq!(eval("A | 0", a), Value::Bool(a));
+            assert_eq!(eval("A & false", a), Value::Bool(false));
+            assert_eq!(eval("A | ⊤", a), Value::Bool(true));
+            assert_eq!(eval("A -> 0", a), Value::Bool(!a));
+        }
+        // * as written: `=` is the loosest equivalence in logic, and an equality in math
+        assert_eq!(
+            parse::<LogicLexer>("A & 1 = A").unwrap(),
+            "(Iff (And A True) A)"
+        );
+        for src in ["A & 1 = A", "A | 0 = A", "A & 0 = 0", "A | 1 = ⊤"] {
+            for a in [false, true] {
+                assert_eq!(eval(src, a), Value::Bool(true), "{src} with A = {a}");
+            }
+        }
+        assert_eq!(
+            parse::<CompleteLexer>("x + 1 = 2").unwrap(),
+            "(Equal (Add X 1) 2)"
+        );
+    }
+
+    #[test]
+    fn test_number_literals() {
+        let lex = |mut lexer: Lexer<'_, CompleteLexer>| -> Vec<String> {
+            let tokens = lexer.tokenize().unwrap();
+            tokens.iter().map(|t| format!("{:?}", t.value)).collect()
+        };
+        [
+            ("6.022e23", vec!["Real { value: 6.022e23, base: 10 }"]),
+            (
+                "1e-3 + 2E+2",
+                vec![
+                    "Real { value: 0.001, base: 10 }",
+                    "Add",
+                    "Real { value: 200.0, base: 10 }",
+                ],
+            ),
+            ("2e", vec!["Natural { value: 2, base: 10 }", "E"]), // the constant `e`
+            ("2i", vec!["Imaginary { value: 2.0 }"]),
+            ("1.5e1i", vec!["Imaginary { value: 15.0 }"]),
+            ("3+4i", vec!["Complex { real: 3.0, imaginary: 4.0 }"]),
+            ("3-4.5i", vec!["Complex { real: 3.0, imaginary: -4.5 }"]),
+            // * not after an operator that binds the real part tighter than the sum
+            (
+                "2*3+4i",
+                vec![
+                    "Natural { value: 2, base: 10 }",
+                    "Multiply",
+                    "Natural { value: 3, base: 10 }",
+                    "Add",
+                    "Imaginary { value: 4.0 }",
+                ],
+            ),
+            (
+                "1-3+4i",
+                vec![
+                    "Natural { value: 1, base: 10 }",
+                    "Subtract",
+                    "Natural { value: 3, base: 10 }",
+                    "Add",
+                    "Imaginary { value: 4.0 }",
+                ],
+            ),
+            (
+                "x == 3+4i",
+                vec!["X", "Equal", "Complex { real: 3.0, imaginary: 4.0 }"],
+            ),
+            (
+                "3 + 4i",
+                vec![
+                    "Natural { value: 3, base: 10 }",
+                    "Add",
+                    "Imaginary { value: 4.0 }",
+                ],
+            ),
+            (
+                "3+4",
+                vec![
+                    "Natural { value: 3, base: 10 }",
+                    "Add",
+                    "Natural { value: 4, base: 10 }",
+                ],
+            ),
+            (
+                "3/4",
+                vec![
+                    "Natural { value: 3, base: 10 }",
+                    "Divide",
+                    "Natural { value: 4, base: 10 }",
+                ],
+            ),
+        ]
+        .iter()
+        .for_each(|(src, tokens)| assert_eq!(lex(Lexer::new(src)), *tokens, "{src}"));
+        [
+            ("3/4", vec!["Rational { numerator: 3, denominator: 4 }"]),
+            ("6/8", vec!["Rational { numerator: 3, denominator: 4 }"]),
+            (
+                "1.5/2",
+                vec![
+                    "Real { value: 1.5, base: 10 }",
+                    "Divide",
+                    "Natural { value: 2, base: 10 }",
+                ],
+            ),
+            (
+                "3 / 4",
+                vec![
+                    "Natural { value: 3, base: 10 }",
+                    "Divide",
+                    "Natural { value: 4, base: 10 }",
+                ],
+            ),
+        ]
+        .iter()
+        .for_each(|(src, tokens)| {
+            assert_eq!(lex(Lexer::new(src).with_rationals(true)), *tokens, "{src}")
+        });
+        // * `1/0` isn't a fraction, so it's read as a division
+        let mut lexer = Lexer::<CompleteLexer>::new("1/0").with_rationals(true);
+        assert_eq!(lexer.get_token_table().unwrap().len(), 3);
+
+        // * the number tower: Natural ⊂ Integer ⊂ Rational ⊂ Real ⊂ Complex
+        let natural = Natural::from_n(7);
+        let integer = Integer::try_from(natural).unwrap();
+        let rational = Rational::from(integer);
+        assert_eq!((rational.numerator(), rational.denominator()), (7, 1));
+        let real = Real::from(Rational::new(-1, 4).unwrap());
+        assert_eq!(real.value(), -0.25);
+        assert_eq!(Complex::from(real), Complex::from_parts(-0.25, 0.0));
+        assert_eq!(
+            Complex::from(Imaginary::from_n(2.0)),
+            Complex::from_parts(0.0, 2.0)
+        );
+        assert!(Integer::try_from(Natural::from_n(usize::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_function_calls() {
+        use crate::eval::*;
+
+        [
+            ("sin(x) + gcd(a, b)", "(Add (Sin X) (Gcd A B))"),
+            ("\\cos(2 * x)^2", "(Power (Cos (Multiply 2 X)) 2)"),
+            (
+                "lcm(gcd(a, 4), abs(-b))",
+                "(Lcm (Gcd A 4) (Abs (Subtract B)))",
+            ),
+            ("-arsinh(x)", "(Subtract (ArSinh X))"),
+            ("SINH(x) / cosh(x)", "(Divide (Sinh X) (Cosh X))"),
+        ]
+        .iter()
+        .for_each(|(src, sexpr)| assert_eq!(parse::<CompleteLexer>(src).unwrap(), *sexpr, "{src}"));
+
+        let parse_err = |src: &str| parse::<CompleteLexer>(src).unwrap_err();
+        assert_eq!(
+            parse_err("x + gcd(a)").to_string(),
+            "Gcd at 1:5 takes 2 argument(s) but 1 were given"
+        );
+        assert!(matches!(
+            parse_err("sin(x, y)"),
+            ParseError::WrongArity {
+                expected: 1,
+                found: 2,
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse_err("sin x"),
+            ParseError::UnexpectedToken { index: 1, .. }
+        ));
+        assert!(matches!(
+            parse_err("gcd(a, )"),
+            ParseError::UnexpectedToken { index: 4, .. }
+        ));
+        assert!(matches!(
+            parse_err("a, b"),
+            ParseError::UnexpectedToken { index: 1, .. }
+        ));
+
+        let eval = |src: &str| {
+            let tokens = Lexer::<CompleteLexer>::new(src).get_token_table().unwrap();
+            let env = Environment::new()
+                .with("x", 0.5)
+                .with("a", 12.0)
+                .with("b", 18.0);
+            let ast = Parser::new(tokens).parse().unwrap();
+            ast.evaluate(&env).unwrap().as_number().unwrap()
+        };
+        [
+            ("sin(x)^2 + cos(x)^2", 1.0),
+            ("gcd(a, b) + lcm(a, b)", 6.0 + 36.0),
+            ("floor(-x) + ceil(x) + abs(-3)", 3.0),
+            ("arctan(tan(x))", 0.5),
+            ("artanh(tanh(x))", 0.5),
+        ]
+        .iter()
+        .for_each(|(src, value)| assert!((eval(src) - value).abs() < 1e-12, "{src}"));
+        assert!(eval("gcd(x, 2)").is_nan());
+    }
+
+    #[test]
+    fn test_big_operators() {
+        use crate::eval::*;
+
+        [
+            ("Σ_{i=1}^{n} i", "(Sum I 1 N I)"),
+            (
+                "\\sum_{k=0}^{n + 1} 2 * k + 1",
+                "(Add (Sum K 0 (Add N 1) (Multiply 2 K)) 1)",
+            ),
+            ("sum(i = 1..n, i^2)", "(Sum I 1 N (Power I 2))"),
+            ("∏_{j=1}^n j", "(Product J 1 N J)"),
+        ]
+        .iter()
+        .for_each(|(src, sexpr)| assert_eq!(parse::<CompleteLexer>(src).unwrap(), *sexpr, "{src}"));
+        assert!(parse::<CompleteLexer>("Σ_{i}^{n} i").is_err());
+        assert!(parse::<CompleteLexer>("sum(i = 1, i)").is_err());
+
+        let eval = |src: &str, n: f64| {
+            let tokens = Lexer::<CompleteLexer>::new(src).get_token_table().unwrap();
+            let ast = Parser::new(tokens).parse().unwrap();
+            ast.evaluate(&Environment::new().with("n", n))
+        };
+        for n in 0..10 {
+            let n = n as f64;
+            let gauss = eval("n * (n + 1) / 2", n).unwrap();
+            assert_eq!(eval("Σ_{i=1}^{n} i", n).unwrap(), gauss);
+            assert_eq!(eval("sum(i = 1..n, i)", n).unwrap(), gauss);
+            assert_eq!(
+                eval("\\prod_{i=1}^{n} i", n).unwrap(),
+                eval("n!", n).unwrap()
+            );
+        }
+        // * a factorial that overflows is infinite right away
+        assert!(eval("n!", 170.0).unwrap().as_number().unwrap().is_finite());
+        assert_eq!(eval("n!", 171.0), Ok(Value::Number(f64::INFINITY)));
+        assert_eq!(eval("n!", 1e18), Ok(Value::Number(f64::INFINITY)));
+        assert_eq!(
+            eval("sum(i = 1..n, i)", 0.5),
+            Err(EvalError::TypeMismatch {
+                token: "Sum".to_string(),
+                expected: "integer"
+            })
+        );
+        // * the identity as written
+        for n in 0..10 {
+            let identity = eval("\\sum_{i=1}^{n} i = n(n+1)/2", n as f64);
+            assert_eq!(identity, Ok(Value::Bool(true)), "{n}");
+        }
+        assert_eq!(
+            eval("sum(i = 1..1e18, i)", 0.0),
+            Err(EvalError::RangeTooLarge {
+                token: "Sum".to_string(),
+                terms: 1_000_000_000_000_000_000
+            })
+        );
+    }
+
+    #[test]
+    fn test_comparisons() {
+        use crate::eval::*;
+
+        [
+            (
+                "x + 1 <= 2 * y",
+                "(LessThanOrEqual (Add X 1) (Multiply 2 Y))",
+            ),
+            ("0 <= x < 10", "(And (LessThanOrEqual 0 X) (LessThan X 10))"),
+            (
+                "a < b < c != d",
+                "(And (And (LessThan A B) (LessThan B C)) (NotEqual C D))",
+            ),
+            ("(x > 3) & B", "(And (GreaterThan X 3) B)"),
+            (
+                "x ≥ 3 ∨ ¬y = 2",
+                "(Or (GreaterThanOrEqual X 3) (Not (Equal Y 2)))",
+            ),
+            ("(a < b) == B", "(Equal (LessThan A B) B)"),
+            // * not a converse implication (`<-`) where comparisons are read
+            ("x<-1", "(LessThan X (Subtract 1))"),
+            ("A ← B", "(ConverseImplies A B)"),
+        ]
+        .iter()
+        .for_each(|(src, sexpr)| assert_eq!(parse::<CompleteLexer>(src).unwrap(), *sexpr, "{src}"));
+
+        let eval = |src: &str, x: f64| {
+            let tokens = Lexer::<CompleteLexer>::new(src).get_token_table().unwrap();
+            let env = Environment::new().with("x", x).with("B", true);
+            Parser::new(tokens).parse().unwrap().evaluate(&env)
+        };
+        for (x, inside) in [(-1.0, false), (0.0, true), (9.5, true), (10.0, false)] {
+            assert_eq!(eval("0 <= x < 10", x), Ok(Value::Bool(inside)), "{x}");
+        }
+        assert_eq!(eval("(x > 3) & B", 4.0), Ok(Value::Bool(true)));
+        assert_eq!(eval("x * 2 == 8 != x", 4.0), Ok(Value::Bool(true)));
+        assert_eq!(eval("(x = 4) = B", 4.0), Ok(Value::Bool(true)));
+        assert_eq!(eval("x<-1", -2.0), Ok(Value::Bool(true)));
+        assert!(matches!(
+            eval("B < x", 4.0),
+            Err(EvalError::TypeMismatch {
+                expected: "number",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_bitwise_operators() {
+        use crate::eval::*;
+
+        [
+            ("(x >> 4) & 0xF", "(And (ShiftRight X 4) 15)"),
+            ("a | b ^ c & d", "(Or A (XOr B (And C D)))"),
+            ("x << 1 + 2", "(ShiftLeft X (Add 1 2))"),
+            ("~x & m == 0", "(Equal (And (Not X) M) 0)"),
+            ("x ROTL 3 bitor 1", "(Or (RotateLeft X 3) 1)"),
+        ]
+        .iter()
+        .for_each(|(src, sexpr)| assert_eq!(parse::<BitLexer>(src).unwrap(), *sexpr, "{src}"));
+        // * the complete lexer keeps `&` as AND, but reads the shifts
+        assert_eq!(
+            parse::<CompleteLexer>("A & x << 2 > 3").unwrap(),
+            "(And A (GreaterThan (ShiftLeft X 2) 3))"
+        );
+
+        let eval = |src: &str, bits: u32| {
+            let tokens = Lexer::<BitLexer>::new(src).get_token_table().unwrap();
+            let env = Environment::new()
+                .with_bit_width(bits)
+                .unwrap()
+                .with("x", 165.0); // 0xA5
+            Parser::new(tokens).parse().unwrap().evaluate(&env)
+        };
+        let number = |src: &str, bits: u32| eval(src, bits).unwrap().as_number().unwrap();
+        let integer = |src: &str, bits: u32| eval(src, bits).unwrap().as_integer().unwrap();
+        assert_eq!(number("(x >> 4) & 0xF", 8), 0xA as f64);
+        assert_eq!(number("x | 0x0F ^ 0xFF", 8), (0xA5 | (0x0F ^ 0xFF)) as f64);
+        assert_eq!(number("~x", 8), 0x5A as f64);
+        assert_eq!(number("~0", 32), u32::MAX as f64);
+        assert_eq!(number("-1 & 0xFFFF", 16), 0xFFFF as f64);
+        assert_eq!(number("x << 4", 8), 0x50 as f64);
+        assert_eq!(number("x << 4", 16), 0xA50 as f64);
+        assert_eq!(number("x >> 8", 8), 0.0);
+        assert_eq!(number("x <<< 4", 8), 0x5A as f64);
+        assert_eq!(number("x >>> 1", 8), 0xD2 as f64);
+        assert_eq!(number("x rotl 8", 8), 0xA5 as f64);
+        // * the count isn't wrapped to the width
+        assert_eq!(integer("1 << 16", 4), 0);
+        assert_eq!(integer("1 << 256", 8), 0);
+        assert_eq!(integer("x >> 260", 8), 0);
+        assert_eq!(integer("x rotl 9", 8), 0x4B);
+        assert_eq!(integer("x rotr 257", 8), 0xD2);
+        for src in ["1 << -1", "x >> -8", "x rotl -1", "x >>> -3"] {
+            assert!(
+                matches!(eval(src, 8), Err(EvalError::NegativeShift { .. })),
+                "{src}"
+            );
+        }
+        // * exact at any width, even past the 53 bits of a float
+        assert_eq!(integer("~0", 64), u64::MAX as i128);
+        assert_eq!(integer("~0 >> 1", 64), i64::MAX as i128);
+        assert_eq!(integer("1 << 63 | 1", 64), (1 << 63) + 1);
+        assert_eq!(
+            eval("(~0 >> 1) == 9223372036854775807", 64),
+            Ok(Value::Bool(true))
+        );
+        assert_eq!(
+            eval("~0 == 18446744073709551614", 64),
+            Ok(Value::Bool(false))
+        );
+        assert_eq!(
+            Environment::new().with_bit_width(65),
+            Err(EvalError::InvalidBitWidth(65))
+        );
+        assert!(Environment::new().with_bit_width(0).is_err());
+        assert!(matches!(
+            eval("x / 2 & 1", 8),
+            Err(EvalError::TypeMismatch {
+                expected: "integer",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_quantifiers() {
+        [
+            ("∀x P(x)", "(ForAll X (P X))"),
+            ("∀x P(x) → Q(x)", "(Implies (ForAll X (P X)) (Q X))"),
+            (
+                "∀x (P(x) → ∃y R(x, y))",
+                "(ForAll X (Implies (P X) (Exists Y (R X Y))))",
+            ),
+            (
+                "forall(x, exists(y, R(x, y)))",
+                "(ForAll X (Exists Y (R X Y)))",
+            ),
+            (
+                "\\forall x \\neg P(x) ∧ A",
+                "(And (ForAll X (Not (P X))) A)",
+            ),
+            ("¬∃x P(x)", "(Not (Exists X (P X)))"),
+        ]
+        .iter()
+        .for_each(|(src, sexpr)| assert_eq!(parse::<LogicLexer>(src).unwrap(), *sexpr, "{src}"));
+
+        let parse_err = |src: &str| parse::<LogicLexer>(src).unwrap_err();
+        assert_eq!(
+            parse_err("∀x ∃x P(x)").to_string(),
+            "`x` at 1:5 is already bound by an enclosing quantifier"
+        );
+        assert_eq!(
+            parse::<CompleteLexer>("∀i sum(i = 1..3, P(i))")
+                .unwrap_err()
+                .to_string(),
+            "`i` at 1:8 is already bound by an enclosing quantifier"
+        );
+        assert_eq!(
+            parse::<CompleteLexer>("Σ_{i=1}^{n} ∏_{i=1}^{i} i")
+                .unwrap_err()
+                .to_string(),
+            "`i` at 1:16 is already bound by an enclosing sum"
+        );
+        assert_eq!(
+            parse::<CompleteLexer>("prod(k = 1..3, ∃k P(k))")
+                .unwrap_err()
+                .to_string(),
+            "`k` at 1:17 is already bound by an enclosing product"
+        );
+        assert!(matches!(
+            parse_err("∀(⊤, Q)"),
+            ParseError::UnexpectedToken { index: 2, .. }
+        ));
+        assert!(matches!(
+            parse_err("∃ ∧ P"),
+            ParseError::UnexpectedToken { index: 1, .. }
+        ));
+        // * sibling scopes can reuse a variable
+        assert!(parse::<LogicLexer>("∀x P(x) ∧ ∀x Q(x)").is_ok());
+
+        let free = |src: &str| {
+            let tokens = Lexer::<LogicLexer>::new(src).get_token_table().unwrap();
+            Parser::new(tokens).parse().unwrap().free_variables()
+        };
+        assert_eq!(free("∀x R(x, y) ∨ P(x)"), vec!["y", "x"]);
+        assert_eq!(free("∀x ∃y R(x, y)"), Vec::<String>::new());
+        assert_eq!(free("A ∧ B → A"), vec!["A", "B"]);
+        let tokens = Lexer::<CompleteLexer>::new("sum(i = 1..n, i * k)")
+            .get_token_table()
+            .unwrap();
+        let ast = Parser::new(tokens).parse().unwrap();
+        assert_eq!(ast.free_variables(), vec!["n", "k"]);
+
+        // * only uppercase and declared names are predicates, a lowercase letter multiplies
+        assert_eq!(
+            parse::<CompleteLexer>("n(n+1)/2").unwrap(),
+            "(Divide (Multiply N (Add N 1)) 2)"
+        );
+        let tokens = Lexer::<LogicLexer>::new("∀x p(x)")
+            .get_token_table()
+            .unwrap();
+        let ast = Parser::new(tokens).with_predicates(["p"]).parse().unwrap();
+        assert_eq!(sexpr(ast.root.as_ref().unwrap()), "(ForAll X (P X))");
+        let tokens = Lexer::<CompleteLexer>::new("carry(x)")
+            .with_multi_letter_names(true)
+            .get_token_table()
+            .unwrap();
+        assert_eq!(
+            Parser::new(tokens).parse().unwrap_err().to_string(),
+            "`carry` at 1:1 is applied to arguments but isn't a predicate \
+             (predicates start with an uppercase letter or are declared)"
+        );
+
+        // * a logic formula has no products: a lowercase letter before a group is an undeclared predicate
+        for src in ["a(b | a)", "a(b ∨ a) & ¬b"] {
+            assert!(
+                matches!(
+                    parse::<LogicLexer>(src),
+                    Err(ParseError::UndeclaredPredicate { ref name, .. }) if name == "a"
+                ),
+                "{src}"
+            );
+        }
+        assert!(parse::<CompleteLexer>("a(b | a)").is_err());
+        let tokens = Lexer::<LogicLexer>::new("a(b | a)")
+            .get_token_table()
+            .unwrap();
+        let ast = Parser::new(tokens).with_predicates(["a"]).parse().unwrap();
+        assert_eq!(sexpr(ast.root.as_ref().unwrap()), "(A (Or B A))");
+
+        let gauss = |n: f64| {
+            let tokens = Lexer::<CompleteLexer>::new("n(n+1)/2")
+                .get_token_table()
+                .unwrap();
+            let env = crate::eval::Environment::new().with("n", n);
+            Parser::new(tokens).parse().unwrap().evaluate(&env)
+        };
+        assert_eq!(gauss(4.0), Ok(crate::eval::Value::Number(10.0)));
+    }
+
+    #[test]
+    fn test_token_kinds() {
+        use OperatorKind::*;
+        use TokenKind::*;
+
+        let kinds: Vec<TokenKind> = Lexer::<CompleteLexer>::new("x + 2 < sin(π) ∧ ∀y ⊤ << 1")
+            .get_token_table()
+            .unwrap()
+            .iter()
+            .map(|token| token.kind())
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Variable,
+                Operator(Math),
+                Number,
+                Operator(Relational),
+                Function,
+                Delimiter,
+                Constant,
+                Delimiter,
+                Operator(Logic),
+                Binder,
+                Variable,
+                Constant,
+                Operator(Bitwise),
+                Number,
+            ]
+        );
+
+        let token: Box<dyn Token> = Box::new(Natural::from_str("0x1F").unwrap());
+        assert!(token.is::<Natural>());
+        assert_eq!(
+            token.downcast_ref::<Natural>().map(Natural::value),
+            Some(31)
+        );
+        assert!(token.as_any().downcast_ref::<Natural>().is_some());
+        assert_eq!(token.clone().downcast_ref::<Integer>(), None);
+
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        struct Custom;
+        impl Token for Custom {
+            fn from_str<S: Into<String>>(_: S) -> Option<Self> {
+                None
+            }
+        }
+        assert_eq!(Custom.kind(), Other);
+    }
+
+    #[test]
+    fn test_ast_clone_and_equality() {
+        use crate::eval::*;
+        use std::collections::HashSet;
+
+        let ast = |src: &str| {
+            let tokens = Lexer::<CompleteLexer>::new(src).get_token_table().unwrap();
+            Parser::new(tokens).parse().unwrap()
+        };
+        let original = ast("x^2 + 2.5 * sin(y) > 3 & B");
+        let copy = original.clone();
+        assert_eq!(copy, original);
+        assert_eq!(copy.to_string(), original.to_string());
+        let env = Environment::new()
+            .with("x", 2.0)
+            .with("y", 0.0)
+            .with("B", true);
+        assert_eq!(copy.evaluate(&env), original.evaluate(&env));
+
+        assert_eq!(ast("(A ∧ B)"), ast("A & B"));
+        assert_ne!(ast("A & B"), ast("B & A"));
+        assert_ne!(ast("2.5"), ast("2.25"));
+        // * the base a number is written in is kept for display only
+        assert_eq!(ast("0xF"), ast("15"));
+        assert_eq!(ast("0b1111 + x"), ast("0o17 + x"));
+        assert_eq!(crate::printer::Printer::new().print(&ast("0xF")), "0xF");
+        // * same spelling, different token types
+        assert_ne!(ast("1"), ast("⊤"));
+        let one: Box<dyn Token> = Box::new(Natural::from_n(1));
+        assert_ne!(one, Box::new(Integer::from_n(1)) as Box<dyn Token>);
+        assert_eq!(one, one.clone());
+
+        let cache: HashSet<AST> = ["A & B", "A ∧ B", "A | B", "¬A", "~A"]
+            .into_iter()
+            .map(ast)
+            .collect();
+        assert_eq!(cache.len(), 3);
+        assert!(cache.contains(&ast("A or B")));
+    }
+
+    #[test]
+    fn test_operator_notation() {
+        use crate::dialect::*;
+
+        assert_eq!(MathOp::Subtract.arity(), 2);
+        assert!(MathOp::Subtract.is_unary() && MathOp::Subtract.is_binary());
+        assert_eq!(MathOp::Root.arity(), 1);
+        assert_eq!(MathOp::Factorial.notation().postfix, Some(25));
+        assert!(MathOp::Power.is_right_associative());
+        assert!(MathOp::Multiply.is_left_associative());
+        assert!(RelationalOp::LessThan.is_non_associative());
+        assert!(LogicOp::And.precedence() > LogicOp::Or.precedence());
+        assert!(LogicOp::Not.is_right_associative());
+
+        // * the dialect overrides the table
+        let xor = |dialect: Dialect| dialect.notation(&LogicOp::XOr).infix;
+        assert_eq!(xor(Dialect::Programmer), Some((4, Associativity::Left)));
+        assert_eq!(xor(Dialect::Textbook), Some((3, Associativity::Left)));
+        let xor: Box<dyn Token> = Box::new(LogicOp::XOr);
+        assert_eq!(
+            Dialect::Textbook.notation_of(xor.as_ref()),
+            Some(Dialect::Textbook.notation(&LogicOp::XOr))
+        );
+        assert_eq!(Dialect::Textbook.notation_of(&Natural::from_n(1)), None);
+    }
+
+    #[test]
+    fn test_printer() {
+        use crate::printer::*;
+
+        fn print<T: TokenRecognizer + Default>(src: &str) -> String {
+            let tokens = Lexer::<T>::new(src).get_token_table().unwrap();
+            Printer::new().print(&Parser::new(tokens).parse().unwrap())
+        }
+        assert_eq!(print::<LogicLexer>("((A & B)) | (C & D)"), "A ∧ B ∨ C ∧ D");
+        assert_eq!(print::<LogicLexer>("(A | B) & ~(C)"), "(A ∨ B) ∧ ¬C");
+        assert_eq!(print::<LogicLexer>("A -> (B -> C)"), "A → B → C");
+        assert_eq!(print::<LogicLexer>("(A -> B) -> C"), "(A → B) → C");
+        assert_eq!(
+            print::<LogicLexer>("(∀x (P(x) & Q)) & ∀y ¬(P(y))"),
+            "∀x (P(x) ∧ Q) ∧ ∀y ¬P(y)"
+        );
+        assert_eq!(
+            print::<CompleteLexer>("(1 + 2) * (3 * 4)"),
+            "(1 + 2) × (3 × 4)"
+        );
+        assert_eq!(
+            print::<CompleteLexer>("(x - y) - (z - 1)"),
+            "x - y - (z - 1)"
+        );
+        assert_eq!(
+            print::<CompleteLexer>("2*(3+4i) - (1-2i)"),
+            "2 × (3+4i) - (1-2i)"
+        );
+        assert_eq!(
+            print::<CompleteLexer>("(2^3)^2 + 2^(3^2)"),
+            "(2 ^ 3) ^ 2 + 2 ^ 3 ^ 2"
+        );
+        assert_eq!(print::<CompleteLexer>("-(x + 1) * (n!)"), "-(x + 1) × n!");
+        assert_eq!(
+            print::<CompleteLexer>("sin((x)) + gcd(a, (b))"),
+            "sin(x) + gcd(a, b)"
+        );
+        assert_eq!(print::<CompleteLexer>("⌊x / 2⌋ + 0xFF"), "⌊x ÷ 2⌋ + 0xFF");
+        assert_eq!(Printer::new().print(&AST::default()), "");
+
+        // * a fraction binds like the division it's written as
+        let fraction = |src: &str| {
+            let tokens = Lexer::<CompleteLexer>::new(src)
+                .with_rationals(true)
+                .get_token_table()
+                .unwrap();
+            Parser::new(tokens).parse().unwrap()
+        };
+        let ast = fraction("(3/4)^2 + 2^(1/2) * 5");
+        let printed = Printer::new().print(&ast);
+        assert_eq!(printed, "(3/4) ^ 2 + 2 ^ (1/2) × 5");
+        assert_eq!(fraction(&printed), ast);
+
+        // * printing and parsing again gives back the same tree
+        fn round_trip<T: TokenRecognizer + Default>(src: &str) {
+            let ast = |src: &str| {
+                Parser::new(Lexer::<T>::new(src).get_token_table().unwrap())
+                    .parse()
+                    .unwrap()
+            };
+            let printed = Printer::new().print(&ast(src));
+            assert_eq!(ast(&printed), ast(src), "{src} was printed as {printed}");
+        }
+        for src in [
+            "A & (B | C) ^ D",
+            "~(A & B) <-> ~A | ~B",
+            "(A -> B) -> (C <- D)",
+            "A ↑ (B ↓ C) ⊙ D",
+            "∃x (P(x) -> ∀y Q(x, y))",
+            "not (A and not B) or C",
+        ] {
+            round_trip::<LogicLexer>(src);
+        }
+        for src in [
+            "1 - (2 - 3) + -4 * (5 % 6)",
+            "(x^2)^3 / √(y + 1) - (n!)!",
+            "sum(i = 1..n, i * 2) + (a < b & b < c)",
+            "x * 2 == 8 != x",
+            "-(2.5) ^ 2 + (3+4i) * 1e-3",
+            "2*3+4i - (1-2i) ^ 2",
+            "⌈x⌉ - ⌊y - 1⌋ + abs(z)",
+        ] {
+            round_trip::<CompleteLexer>(src);
+        }
+    }
+
+    #[test]
+    fn test_render_styles() {
+        use crate::printer::*;
+
+        let render = |token: &dyn Token| {
+            [Style::Unicode, Style::Ascii, Style::Latex, Style::Name]
+                .map(|style| token.render(style))
+        };
+        assert_eq!(render(&LogicOp::And), ["∧", "&", "\\land", "and"]);
+        assert_eq!(
+            render(&LogicOp::Implies),
+            ["→", "->", "\\implies", "implies"]
+        );
+        assert_eq!(
+            render(&LogicOp::ConverseImplies),
+            ["←", "<-", "\\impliedby", "converse implies"]
+        );
+        assert_eq!(render(&MathOp::Multiply), ["×", "*", "\\times", "multiply"]);
+        assert_eq!(render(&MathOp::Root), ["√", "\\sqrt", "\\sqrt", "root"]);
+        assert_eq!(
+            render(&RelationalOp::LessThanOrEqual),
+            ["≤", "<=", "\\leq", "less than or equal"]
+        );
+        assert_eq!(render(&BitOp::ShiftLeft), ["<<", "<<", "<<", "shl"]);
+        assert_eq!(
+            render(&MathConst::EulerGamma),
+            ["γ", "\\gamma", "\\gamma", "euler gamma"]
+        );
+        assert_eq!(render(&GreekUpperAlpha::Alpha), ["Α", "Α", "Α", "alpha"]);
+        assert_eq!(
+            render(&PhysicConst::SpeedOfLight),
+            ["\\c", "\\c", "\\c", "speed of light"]
+        );
+        assert_eq!(
+            render(&Quantifier::ForAll),
+            ["∀", "forall", "\\forall", "forall"]
+        );
+        assert_eq!(render(&BoolConst::True), ["⊤", "true", "\\top", "true"]);
+        assert_eq!(render(&Real::from_n(2.5)), ["2.5"; 4]);
+        let x_1 = Identifier::from_str("x_1").unwrap();
+        assert_eq!(render(&x_1), ["x_1"; 4]);
+
+        // * the printed expression can be read again in any style the lexer knows
+        let ast = |src: &str| {
+            let tokens = Lexer::<LogicLexer>::new(src).get_token_table().unwrap();
+            Parser::new(tokens).parse().unwrap()
+        };
+        let original = ast("¬(A ∧ B) ↔ ¬A ∨ (B ⊕ C) → D");
+        let print = |style| Printer::new().with_style(style).print(&original);
+        assert_eq!(print(Style::Ascii), "!(A & B) <-> !A | (B xor C) -> D");
+        assert_eq!(
+            print(Style::Latex),
+            "\\neg (A \\land B) \\iff \\neg A \\lor (B \\oplus C) \\implies D"
+        );
+        assert_eq!(
+            print(Style::Name),
+            "not (A and B) iff not A or (B xor C) implies D"
+        );
+        for style in [Style::Unicode, Style::Ascii, Style::Latex, Style::Name] {
+            assert_eq!(ast(&print(style)), original, "{style:?}");
+        }
+
+        // * letters and constants without an ASCII or LaTeX spelling keep a spelling of their own
+        let ast = |src: &str| {
+            let tokens = Lexer::<CompleteLexer>::new(src).get_token_table().unwrap();
+            Parser::new(tokens).parse().unwrap()
+        };
+        let original = ast("Α + α * √(Γ - ∞) ≤ \\c ^ 2 + γ × e + ∑(i = 1..n, θ)");
+        let print = |style| Printer::new().with_style(style).print(&original);
+        assert_eq!(
+            print(Style::Ascii),
+            "Α + \\alpha * \\sqrt (\\Gamma - \\infty) <= \\c ^ 2 + \\gamma * e + sum(i = 1..n, \\theta)"
+        );
+        for style in [Style::Unicode, Style::Ascii, Style::Latex] {
+            assert_eq!(ast(&print(style)), original, "{style:?}: {}", print(style));
+        }
+    }
+
+    #[test]
+    fn test_category_constructors() {
+        let op = Operator::from("^").unwrap();
+        assert_eq!(op.kind(), TokenKind::Operator(OperatorKind::Math));
+        assert_eq!(op.as_any().downcast_ref::<MathOp>(), Some(&MathOp::Power));
+        let op = Operator::from(String::from("&")).unwrap();
+        assert_eq!(op.as_any().downcast_ref::<LogicOp>(), Some(&LogicOp::And));
+        assert_eq!(
+            Operator::from("<<").unwrap().kind(),
+            TokenKind::Operator(OperatorKind::Bitwise)
+        );
+        assert!(Operator::from("x").is_none());
+
+        let number = |src: &str| Number::from(src).map(|n| n.text());
+        assert!(
+            Number::from("3")
+                .unwrap()
+                .as_any()
+                .downcast_ref::<Natural>()
+                .is_some()
+        );
+        assert!(
+            Number::from("-3")
+                .unwrap()
+                .as_any()
+                .downcast_ref::<Integer>()
+                .is_some()
+        );
+        assert!(
+            Number::from("2.5")
+                .unwrap()
+                .as_any()
+                .downcast_ref::<Real>()
+                .is_some()
+        );
+        assert!(
+            Number::from("3+4i")
+                .unwrap()
+                .as_any()
+                .downcast_ref::<Complex>()
+                .is_some()
+        );
+        assert_eq!(number("0xFF"), Some("0xFF".to_string()));
+        assert_eq!(number("6/8"), Some("3/4".to_string()));
+        assert_eq!(number("2i"), Some("2i".to_string()));
+        assert_eq!(number("pi"), None);
+
+        let variable = Variable::from("π").unwrap();
+        assert_eq!(
+            variable.as_any().downcast_ref::<GreekAlpha>(),
+            Some(&GreekAlpha::Pi)
+        );
+        assert_eq!(Variable::from("∞").unwrap().kind(), TokenKind::Constant);
+        assert_eq!(Function::from("∀").unwrap().kind(), TokenKind::Binder);
+        assert_eq!(
+            Delimiter::from("⌈")
+                .unwrap()
+                .as_any()
+                .downcast_ref::<Bracket>(),
+            Some(&Bracket::OpenCeil)
+        );
+
+        // * every symbol once, in the order they are tried
+        let symbols = Operator::symbols();
+        assert_eq!(symbols[..3], ["+", "-", "*"]);
+        assert_eq!(symbols.iter().filter(|&&s| s == "&").count(), 1);
+        for symbol in &symbols {
+            assert!(Operator::from(*symbol).is_some(), "{symbol}");
+        }
+        assert!(Variable::symbols().contains(&"\\infty"));
+        assert!(Function::symbols().contains(&"arcsin"));
+        assert!(Number::symbols().is_empty());
+    }
+
+    #[test]
+    fn test_parse_error_span() {
+        let tokens = Lexer::<LogicLexer>::new("A &\n  & B")
+            .get_token_table()
+            .unwrap();
+        match Parser::new(tokens).parse() {
+            Err(ParseError::UnexpectedToken { span, .. }) => {
+                assert_eq!((span.start, span.line, span.column), (6, 2, 3))
+            }
+            other => panic!("expected an unexpected token error, got {other:?}"),
+        }
+    }
+
     #[test] // Indicates that this is a test
     fn test_pair_brackets() {
-        // vec![
-        //     ("A & (B & C)", true),
-        //     ("A & (B & C) & D", true),
-        //     ("(a + b) * (c - d)", true),
-        //     ("(a + b) * (c - d]", false),
-        //     ("x + y] * [z - w]", false),
-        //     ("x + y] * [z - w)", false),
-        //     ("1, 2, 3, 4}", false),
-        //     ("1, 2, 3, 4]", false),
-        //     ("html></html>", false),
-        //     ("html></htm>", false),
-        //     ("<html></htm>", true),
-        //     ("(", false),
-        //     ("[", false),
-        //     ("{", false),
-        //     ("<", false),
-        //     ("[{()}]", true),
-        //     ("{[()]}>", false),
-        //     (" ", true),
-        //     ("", true),
-        // ].iter().for_each(|(src, result)|
-        //     assert_eq!(check_pair_brackets(&src.chars().collect::<Vec<char>>()), *result)
-        // );
+        let paired = |src: &str| {
+            check_brackets(&Lexer::<CompleteLexer>::new(src).get_token_table().unwrap())
+        };
+        [
+            ("A & (B & C)", true),
+            ("A & (B & C) & D", true),
+            ("(a + b) * (c - d)", true),
+            ("(a + b) * (c - d]", false),
+            ("x + y] * [z - w]", false),
+            ("x + y] * [z - w)", false),
+            ("1, 2, 3, 4}", false),
+            ("1, 2, 3, 4]", false),
+            ("html⟩⟨/html⟩", false),
+            ("html⟩⟨/htm⟩", false),
+            ("⟨html⟩⟨/htm⟩", true),
+            ("(", false),
+            ("[", false),
+            ("{", false),
+            ("⟨", false),
+            ("[{()}]", true),
+            ("{[()]}⟩", false),
+            (" ", true),
+            ("", true),
+        ]
+        .iter()
+        .for_each(|(src, result)| assert_eq!(paired(src).is_ok(), *result, "{src}"));
+
+        // * errors point at the offending brackets
+        let errors = paired("(a]\n  ⌈b").unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].to_string(),
+            "`]` at 1:3 doesn't close the `(` at 1:1"
+        );
+        assert_eq!(errors[1].to_string(), "unclosed `⌈` at 2:3");
+        match &paired("a)").unwrap_err()[0] {
+            BracketError::Unopened { bracket, span } => {
+                assert_eq!((bracket, span.range()), (&Bracket::CloseParen, 1..2))
+            }
+            other => panic!("expected an unopened bracket, got {other:?}"),
+        }
+
+        // * the parser groups, and keeps the floor, ceiling and absolute value groups as nodes
+        [
+            ("(A | B) & C", "(And (Or A B) C)"),
+            ("[{A}] -> ⟨B⟩", "(Implies A B)"),
+        ]
+        .iter()
+        .for_each(|(src, sexpr)| assert_eq!(parse::<LogicLexer>(src).unwrap(), *sexpr, "{src}"));
+        [
+            ("(1 + 2) * 3", "(Multiply (Add 1.0 2.0) 3.0)"),
+            ("⌊2.5⌋ + |-3|", "(Add (OpenFloor 2.5) (Bar (Subtract 3.0)))"),
+            ("(3)!", "(Factorial 3.0)"),
+        ]
+        .iter()
+        .for_each(|(src, sexpr)| assert_eq!(parse::<MathLexer>(src).unwrap(), *sexpr, "{src}"));
+        assert!(matches!(
+            parse::<MathLexer>("(1 + 2]"),
+            Err(ParseError::Bracket(BracketError::Mismatched { .. }))
+        ));
+        assert!(matches!(
+            parse::<MathLexer>("()"),
+            Err(ParseError::UnexpectedToken { index: 1, .. })
+        ));
+
+        let eval = |src: &str| {
+            let tokens = Lexer::<MathLexer>::new(src).get_token_table().unwrap();
+            let ast = Parser::new(tokens).parse().unwrap();
+            ast.evaluate(&crate::eval::Environment::new()).unwrap()
+        };
+        [
+            ("⌊2.7⌋", 2.0),
+            ("⌈2.1⌉", 3.0),
+            ("\\lfloor -2.5 \\rfloor", -3.0),
+            ("|1 - 4| * 2", 6.0),
+            ("(1 + 2) * 3", 9.0),
+            ("⌈7 / 2⌉ - ⌊7 / 2⌋", 1.0),
+        ]
+        .iter()
+        .for_each(|(src, value)| assert_eq!(eval(src).as_number(), Some(*value), "{src}"));
+
+        // * where `|` is also OR, it's a bar where an operand starts or an open bar ends
+        [
+            ("|x| * 2", "(Multiply (Bar X) 2)"),
+            ("|y - 1| + |x|", "(Add (Bar (Subtract Y 1)) (Bar X))"),
+            ("A | B", "(Or A B)"),
+            (
+                "(A | B) & |x| > 1",
+                "(And (Or A B) (GreaterThan (Bar X) 1))",
+            ),
+            (
+                "|x| < 1 | |y| < 1",
+                "(Or (LessThan (Bar X) 1) (LessThan (Bar Y) 1))",
+            ),
+        ]
+        .iter()
+        .for_each(|(src, sexpr)| assert_eq!(parse::<CompleteLexer>(src).unwrap(), *sexpr, "{src}"));
+        let tokens = Lexer::<CompleteLexer>::new("|x| * 2")
+            .get_token_table()
+            .unwrap();
+        let ast = Parser::new(tokens).parse().unwrap();
+        let env = crate::eval::Environment::new().with("x", -3.0);
+        assert_eq!(ast.evaluate(&env).unwrap().as_number(), Some(6.0));
+        assert_eq!(crate::printer::Printer::new().print(&ast), "|x| × 2");
     }
 
     // /// Test if the notation for the AST is correct.
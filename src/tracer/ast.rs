@@ -7,7 +7,9 @@ use crate::tracer::tokens::*;
 use std::fmt::{self, Debug, Display, Formatter};
 
 /// A node in the Abstract Syntax Tree
-#[derive(Debug)]
+///
+/// Two nodes are equal if their tokens are of the same type and value, and so are their children.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Node {
     /// The token contained in this node (as a trait object)
     pub token: Box<dyn Token>,
@@ -41,6 +43,11 @@ impl Node {
         }
     }
 
+    /// Creates a new node from an already boxed token and its children
+    pub fn boxed(token: Box<dyn Token>, children: Vec<Node>) -> Self {
+        Self { token, children }
+    }
+
     /// Adds a child node to this node
     pub fn add_child(&mut self, child: Node) {
         self.children.push(child);
@@ -50,6 +57,40 @@ impl Node {
     pub fn is_leaf(&self) -> bool {
         self.children.is_empty()
     }
+
+    /// Returns the variables that aren't bound by an enclosing quantifier or big operator,
+    /// in order of appearance and without repetitions.
+    ///
+    /// The name of a predicate (the `P` of `P(x)`) isn't a variable.
+    pub fn free_variables(&self) -> Vec<String> {
+        fn collect(node: &Node, bound: &mut Vec<String>, free: &mut Vec<String>) {
+            let token = node.token.as_ref();
+            let is_binder = token.downcast_ref::<Quantifier>().is_some()
+                || token.downcast_ref::<BigOp>().is_some();
+            if let (true, Some((variable, rest))) = (is_binder, node.children.split_first()) {
+                // * the bounds of a big operator are outside of the scope of its variable
+                let (outside, scope) = rest.split_at(rest.len().saturating_sub(1));
+                outside.iter().for_each(|child| collect(child, bound, free));
+                let depth = bound.len();
+                bound.extend(variable_name(variable.token.as_ref()));
+                scope.iter().for_each(|child| collect(child, bound, free));
+                bound.truncate(depth);
+                return;
+            }
+            match variable_name(token).filter(|_| node.is_leaf()) {
+                Some(name) if !bound.contains(&name) && !free.contains(&name) => free.push(name),
+                Some(_) => {}
+                None => node
+                    .children
+                    .iter()
+                    .for_each(|child| collect(child, bound, free)),
+            }
+        }
+
+        let mut free = Vec::new();
+        collect(self, &mut Vec::new(), &mut free);
+        free
+    }
 }
 
 impl Display for Node {
@@ -96,33 +137,8 @@ impl Display for Node {
     }
 }
 
-impl Clone for Node {
-    fn clone(&self) -> Self {
-        let token_str = self.token.to_string();
-        let placeholder_token = PlaceholderToken(token_str);
-        Self {
-            token: Box::new(placeholder_token),
-            children: self.children.clone(),
-        }
-    }
-}
-
-/// A simple placeholder token used for cloning
-#[derive(Debug, Clone)]
-struct PlaceholderToken(String);
-
-impl Token for PlaceholderToken {
-    fn from_str<S: Into<String>>(_string: S) -> Option<Self> {
-        None // Placeholder tokens can't be created from strings
-    }
-
-    fn to_string(&self) -> String {
-        self.0.clone()
-    }
-}
-
 /// Represents a complete Abstract Syntax Tree
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AST {
     pub root: Option<Node>,
 }
@@ -136,6 +152,12 @@ impl Display for AST {
     }
 }
 
+impl Default for AST {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AST {
     pub fn new() -> Self {
         Self { root: None }
@@ -152,4 +174,13 @@ impl AST {
     pub fn is_empty(&self) -> bool {
         self.root.is_none()
     }
+
+    /// Returns the variables of the tree that aren't bound by a quantifier or big operator
+    /// (see [`Node::free_variables`]).
+    pub fn free_variables(&self) -> Vec<String> {
+        self.root
+            .as_ref()
+            .map(Node::free_variables)
+            .unwrap_or_default()
+    }
 }
@@ -0,0 +1,127 @@
+//! Dialects resolve the symbols that don't mean the same in every notation.
+//!
+//! The same proposition can be written in many ways: a discrete math textbook reads `^` as `∧`,
+//! a programmer reads it as XOR, and an electronics engineer writes `A·B + C`.
+//! A [`Dialect`] tells the [`Lexer`](crate::lexer::Lexer) which token a symbol stands for, and
+//! the [`Parser`](crate::parser::Parser) how tight each operator binds.
+//!
+//! | Symbol | Textbook | Programmer | Electronics |
+//! |--------|----------|------------|-------------|
+//! | `^`    | AND      | XOR        | XOR         |
+//! | `*`    | AND      | -          | AND         |
+//! | `+`    | OR       | -          | OR          |
+//! | `e`, `π`, `τ`, `φ`, `γ` | constant | variable | variable |
+
+use crate::tracer::tokens::*;
+
+/// A notation used to write propositions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    /// Discrete math notation (`∧`, `∨`, `¬`, `⊕`), where `^` stands for `∧`.
+    ///
+    /// Letters that name a mathematical constant (`e`, `π`, ...) are read as that constant,
+    /// and XOR binds as loose as OR.
+    #[default]
+    Textbook,
+    /// Programming language notation (`&`, `|`, `!`, `^`), where `^` is XOR.
+    ///
+    /// `+` and `*` are never logic operators, every letter is a variable, and the operators
+    /// bind like in C (`&` before `^` before `|`).
+    Programmer,
+    /// Boolean algebra as used in digital electronics (`A*B + C`, `A ^ B`).
+    ///
+    /// Every letter is a variable, and the operators bind AND before XOR before OR.
+    Electronics,
+}
+
+impl Dialect {
+    /// Returns the symbols this dialect reads differently from the default [`LogicOp`] table.
+    ///
+    /// A `None` operator means the symbol isn't a logic operator at all in this dialect.
+    pub fn logic_overrides(&self) -> &'static [(&'static str, Option<LogicOp>)] {
+        match self {
+            Dialect::Textbook => &[("^", Some(LogicOp::And))],
+            Dialect::Programmer => &[("*", None), ("⋅", None), ("+", None)],
+            Dialect::Electronics => &[],
+        }
+    }
+
+    /// Returns true if the letters that name a constant (`e`, `π`, ...) are read as constants.
+    pub fn reads_constants(&self) -> bool {
+        matches!(self, Dialect::Textbook)
+    }
+
+    /// Chooses which of the tokens a symbol could be read as is the one meant in this dialect.
+    ///
+    /// The candidates are given in the recognizer's priority order. Returns `None` if the symbol
+    /// has no meaning in this dialect.
+    pub fn resolve(&self, symbol: &str, candidates: Vec<Box<dyn Token>>) -> Option<Box<dyn Token>> {
+        let mut candidates = candidates
+            .into_iter()
+            .filter_map(|token| self.reinterpret(symbol, token));
+        match self.reads_constants() {
+            true => {
+                let candidates: Vec<Box<dyn Token>> = candidates.collect();
+                let constant = candidates
+                    .iter()
+                    .position(|token| token.downcast_ref::<MathConst>().is_some());
+                candidates.into_iter().nth(constant.unwrap_or(0))
+            }
+            false => candidates.next(),
+        }
+    }
+
+    /// Applies this dialect's overrides to a token read from `symbol`.
+    fn reinterpret(&self, symbol: &str, token: Box<dyn Token>) -> Option<Box<dyn Token>> {
+        if token.downcast_ref::<LogicOp>().is_none() {
+            return Some(token);
+        }
+        match self.logic_overrides().iter().find(|(s, _)| *s == symbol) {
+            Some((_, Some(op))) => Some(Box::new(op.clone())),
+            Some((_, None)) => None,
+            None => Some(token),
+        }
+    }
+
+    /// Returns how an operator is written and how tight it binds in this dialect.
+    ///
+    /// That's the [`Notation`] declared by the operator, except for XOR (and XNOR), that binds
+    /// as loose as OR in the textbook notation and between AND and OR in the others.
+    pub fn notation<T: OperatorTrait + 'static>(&self, op: &T) -> Notation {
+        let notation = op.notation();
+        match (self, op.as_any().downcast_ref::<LogicOp>()) {
+            (Dialect::Textbook, Some(LogicOp::XOr | LogicOp::XNOr)) => {
+                notation.infix(3, Associativity::Left)
+            }
+            _ => notation,
+        }
+    }
+
+    /// Returns the precedence the body of a quantifier without parentheses binds at: the same as
+    /// a negation, so `∀x P(x) → Q(x)` reads as `(∀x P(x)) → Q(x)`.
+    pub fn quantifier_body_precedence(&self) -> u8 {
+        self.notation(&LogicOp::Not).prefix.unwrap_or_default()
+    }
+
+    /// Returns the precedence the body of a big operator in the `Σ_{i=1}^{n} body` form binds at:
+    /// the same as a product, so `Σ_{i=1}^{n} 2 * i + 1` reads as `(Σ_{i=1}^{n} 2 * i) + 1`.
+    pub fn big_op_body_precedence(&self) -> u8 {
+        self.notation(&MathOp::Multiply)
+            .infix
+            .map_or(0, |(precedence, _)| precedence)
+    }
+
+    /// Returns the [`notation`](Self::notation) of a token, if it's an operator.
+    pub fn notation_of(&self, token: &dyn Token) -> Option<Notation> {
+        if let Some(op) = token.downcast_ref::<MathOp>() {
+            return Some(self.notation(op));
+        }
+        if let Some(op) = token.downcast_ref::<LogicOp>() {
+            return Some(self.notation(op));
+        }
+        if let Some(op) = token.downcast_ref::<RelationalOp>() {
+            return Some(self.notation(op));
+        }
+        token.downcast_ref::<BitOp>().map(|op| self.notation(op))
+    }
+}
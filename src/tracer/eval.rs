@@ -0,0 +1,578 @@
+//! Evaluation of an [`AST`] for some values of its variables.
+//!
+//! Every node is evaluated to a [`Value`]: logic operators work on booleans, math operators and
+//! constants on numbers, and bitwise operators on exact integers. Variables are looked up by their symbol (`A`, `x`, `x_1`, `carry`) in an
+//! [`Environment`].
+//!
+//! # Example
+//!
+//! ```
+//! use logic_tracer::eval::*;
+//! use logic_tracer::lexer::*;
+//! use logic_tracer::parser::*;
+//!
+//! let tokens = Lexer::<LogicLexer>::new("A -> B").get_token_table().unwrap();
+//! let ast = Parser::new(tokens).parse().unwrap();
+//!
+//! let env = Environment::new().with("A", true).with("B", false);
+//! assert_eq!(ast.evaluate(&env), Ok(Value::Bool(false)));
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+use crate::tracer::{ast::*, tokens::*};
+
+/// The result of evaluating a node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Number(f64),
+    /// An exact integer (a literal or the result of a bitwise operator).
+    ///
+    /// Other operators read it as a [`Number`](Value::Number), comparisons keep it exact.
+    Integer(i128),
+}
+
+impl Value {
+    /// Returns the boolean, if the value is one.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the number, if the value is one (an integer is converted, maybe losing precision).
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Number(value) => Some(*value),
+            Value::Integer(value) => Some(*value as f64),
+            Value::Bool(_) => None,
+        }
+    }
+
+    /// Returns the integer, if the value is one (or a number without a fractional part).
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Value::Integer(value) => Some(*value),
+            Value::Number(value) if value.fract() == 0.0 => Some(*value as i128),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Number(value)
+    }
+}
+
+impl From<i128> for Value {
+    fn from(value: i128) -> Self {
+        Value::Integer(value)
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Bool(value) => write!(f, "{value}"),
+            Value::Number(value) => write!(f, "{value}"),
+            Value::Integer(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Errors that can occur while evaluating an [`AST`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    /// The AST has no root.
+    EmptyTree,
+    /// A variable has no value in the [`Environment`].
+    UnboundVariable(String),
+    /// An operator got an operand of the wrong type (e.g. `A & 2`).
+    TypeMismatch {
+        /// Debug representation of the operator.
+        token: String,
+        /// The type the operator expected (`"bool"`, `"number"` or `"integer"`).
+        expected: &'static str,
+    },
+    /// A node has a token that can't be evaluated with that many operands.
+    Unsupported {
+        /// Debug representation of the token.
+        token: String,
+        /// Number of operands (children) of the node.
+        operands: usize,
+    },
+    /// The integers of the bitwise operators can't have this width (it must be in `1..=64`).
+    InvalidBitWidth(u32),
+    /// A shift or a rotation by a negative number of bits (e.g. `1 << -1`).
+    NegativeShift {
+        /// Debug representation of the operator.
+        token: String,
+        /// The number of bits.
+        count: i128,
+    },
+    /// A sum or product ranges over more than [`MAX_BIG_OP_TERMS`] values.
+    RangeTooLarge {
+        /// Debug representation of the big operator.
+        token: String,
+        /// Number of values in the range.
+        terms: u128,
+    },
+}
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::EmptyTree => write!(f, "can't evaluate an empty AST"),
+            EvalError::UnboundVariable(name) => write!(f, "variable `{name}` has no value"),
+            EvalError::TypeMismatch { token, expected } => {
+                write!(f, "{token} expects a {expected} operand")
+            }
+            EvalError::Unsupported { token, operands } => {
+                write!(f, "can't evaluate {token} with {operands} operand(s)")
+            }
+            EvalError::InvalidBitWidth(bits) => {
+                write!(f, "bit width must be in 1..=64, got {bits}")
+            }
+            EvalError::NegativeShift { token, count } => {
+                write!(f, "{token} by a negative number of bits ({count})")
+            }
+            EvalError::RangeTooLarge { token, terms } => write!(
+                f,
+                "{token} over {terms} terms exceeds the limit of {MAX_BIG_OP_TERMS}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// The values of the variables of an expression, keyed by their symbol.
+///
+/// Also holds the width, in bits, of the integers the bitwise operators work on (32 by default).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Environment {
+    values: HashMap<String, Value>,
+    bit_width: u32,
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Self {
+            values: HashMap::new(),
+            bit_width: 32,
+        }
+    }
+}
+
+impl Environment {
+    /// Creates an environment without any variable.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the width of the integers the bitwise operators work on.
+    ///
+    /// Returns [`EvalError::InvalidBitWidth`] if `bits` isn't in `1..=64`.
+    pub fn with_bit_width(mut self, bits: u32) -> Result<Self, EvalError> {
+        if !(1..=64).contains(&bits) {
+            return Err(EvalError::InvalidBitWidth(bits));
+        }
+        self.bit_width = bits;
+        Ok(self)
+    }
+
+    /// Returns the width of the integers the bitwise operators work on.
+    pub fn bit_width(&self) -> u32 {
+        self.bit_width
+    }
+
+    /// Sets the value of a variable.
+    pub fn with<S: Into<String>, V: Into<Value>>(mut self, name: S, value: V) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Sets the value of a variable, returning its previous value.
+    pub fn set<S: Into<String>, V: Into<Value>>(&mut self, name: S, value: V) -> Option<Value> {
+        self.values.insert(name.into(), value.into())
+    }
+
+    /// Returns the value of a variable.
+    pub fn get(&self, name: &str) -> Option<Value> {
+        self.values.get(name).copied()
+    }
+}
+
+impl AST {
+    /// Evaluates the tree for the values of the variables in `env`.
+    pub fn evaluate(&self, env: &Environment) -> Result<Value, EvalError> {
+        self.root
+            .as_ref()
+            .ok_or(EvalError::EmptyTree)?
+            .evaluate(env)
+    }
+}
+
+impl Node {
+    /// Evaluates the subtree of this node for the values of the variables in `env`.
+    pub fn evaluate(&self, env: &Environment) -> Result<Value, EvalError> {
+        let token = self.token.as_ref();
+        if let Some(op) = token.downcast_ref::<BigOp>() {
+            return big_op_value(op, &self.children, env);
+        }
+        if token.downcast_ref::<Quantifier>().is_some() {
+            // * quantifiers range over a domain, which an environment doesn't have
+            return Err(EvalError::Unsupported {
+                token: format!("{token:?}"),
+                operands: self.children.len(),
+            });
+        }
+        let operands = self
+            .children
+            .iter()
+            .map(|child| child.evaluate(env))
+            .collect::<Result<Vec<Value>, EvalError>>()?;
+        if let Some(op) = token.downcast_ref::<BitOp>() {
+            return bit_value(op, &operands, env.bit_width());
+        }
+
+        let value = match operands.as_slice() {
+            _ if token.kind() == TokenKind::Function => function_value(token, &operands)?,
+            [] => leaf_value(token, env)?,
+            [a] => unary_value(token, *a)?,
+            [a, b] => binary_value(token, *a, *b)?,
+            _ => None,
+        };
+        value.ok_or_else(|| EvalError::Unsupported {
+            token: format!("{token:?}"),
+            operands: operands.len(),
+        })
+    }
+}
+
+/// The largest number of terms a sum or product is evaluated over.
+pub const MAX_BIG_OP_TERMS: u128 = 1_000_000;
+
+/// Value of a sum or product of the body for every integer value of the bound variable.
+///
+/// The children are the variable, the bounds and the body; an empty range gives
+/// the identity of the operator (`0` for a sum, `1` for a product).
+fn big_op_value(op: &BigOp, children: &[Node], env: &Environment) -> Result<Value, EvalError> {
+    let unsupported = || EvalError::Unsupported {
+        token: format!("{op:?}"),
+        operands: children.len(),
+    };
+    let [variable, lower, upper, body] = children else {
+        return Err(unsupported());
+    };
+    let name = variable_name(variable.token.as_ref()).ok_or_else(unsupported)?;
+    let (lower, upper) = (
+        integer_operand(op, lower.evaluate(env)?)?,
+        integer_operand(op, upper.evaluate(env)?)?,
+    );
+
+    let terms = upper.saturating_sub(lower).saturating_add(1).max(0) as u128;
+    if terms > MAX_BIG_OP_TERMS {
+        return Err(EvalError::RangeTooLarge {
+            token: format!("{op:?}"),
+            terms,
+        });
+    }
+
+    let mut scope = env.clone();
+    let mut total = match op {
+        BigOp::Sum => 0.0,
+        BigOp::Product => 1.0,
+    };
+    for i in lower..=upper {
+        scope.set(name.as_str(), i);
+        let term = number_operand(op, body.evaluate(&scope)?)?;
+        match op {
+            BigOp::Sum => total += term,
+            BigOp::Product => total *= term,
+        }
+    }
+    Ok(Value::Number(total))
+}
+
+/// Reads `value` as a boolean operand of `token`.
+fn bool_operand(token: &dyn Token, value: Value) -> Result<bool, EvalError> {
+    value.as_bool().ok_or_else(|| EvalError::TypeMismatch {
+        token: format!("{token:?}"),
+        expected: "bool",
+    })
+}
+
+/// Reads `value` as a numeric operand of `token`.
+fn number_operand(token: &dyn Token, value: Value) -> Result<f64, EvalError> {
+    value.as_number().ok_or_else(|| EvalError::TypeMismatch {
+        token: format!("{token:?}"),
+        expected: "number",
+    })
+}
+
+/// Reads `value` as an integer operand of `token`.
+fn integer_operand(token: &dyn Token, value: Value) -> Result<i128, EvalError> {
+    number_operand(token, value)?;
+    value.as_integer().ok_or_else(|| EvalError::TypeMismatch {
+        token: format!("{token:?}"),
+        expected: "integer",
+    })
+}
+
+/// Value of a bitwise operator over integers of `width` bits.
+///
+/// The operands are wrapped to the width as two's complement and the result is read as unsigned,
+/// so `~0` is `255` for a width of 8. The count of a shift or a rotation isn't wrapped:
+/// shifting by the width or more gives `0`, and a negative count is an error.
+fn bit_value(op: &BitOp, operands: &[Value], width: u32) -> Result<Value, EvalError> {
+    let mask = u64::MAX >> (64 - width);
+    let operands = operands
+        .iter()
+        .map(|value| integer_operand(op, *value))
+        .collect::<Result<Vec<i128>, EvalError>>()?;
+    let wrap = |value: i128| value as u64 & mask;
+    let count = |count: i128| match count < 0 {
+        true => Err(EvalError::NegativeShift {
+            token: format!("{op:?}"),
+            count,
+        }),
+        false => Ok(count as u128),
+    };
+    let shift = |a: u64, b: u128, left: bool| match (b < width as u128, left) {
+        (false, _) => 0,
+        (true, true) => a << b,
+        (true, false) => a >> b,
+    };
+    let rotate = |a: u64, b: u128, left: bool| {
+        let b = b % width as u128;
+        shift(a, b, left) | shift(a, width as u128 - b, !left)
+    };
+    let value = match (op, operands.as_slice()) {
+        (BitOp::Not, [a]) => !wrap(*a),
+        (BitOp::And, [a, b]) => wrap(*a) & wrap(*b),
+        (BitOp::Or, [a, b]) => wrap(*a) | wrap(*b),
+        (BitOp::XOr, [a, b]) => wrap(*a) ^ wrap(*b),
+        (BitOp::ShiftLeft, [a, b]) => shift(wrap(*a), count(*b)?, true),
+        (BitOp::ShiftRight, [a, b]) => shift(wrap(*a), count(*b)?, false),
+        (BitOp::RotateLeft, [a, b]) => rotate(wrap(*a), count(*b)?, true),
+        (BitOp::RotateRight, [a, b]) => rotate(wrap(*a), count(*b)?, false),
+        _ => {
+            return Err(EvalError::Unsupported {
+                token: format!("{op:?}"),
+                operands: operands.len(),
+            });
+        }
+    };
+    Ok(Value::Integer((value & mask) as i128))
+}
+
+/// Value of a leaf: a number, a constant or a variable.
+fn leaf_value(token: &dyn Token, env: &Environment) -> Result<Option<Value>, EvalError> {
+    if let Some(number) = token.downcast_ref::<Natural>() {
+        return Ok(Some(Value::Integer(number.value() as i128)));
+    }
+    if let Some(number) = token.downcast_ref::<Integer>() {
+        return Ok(Some(Value::Integer(number.value() as i128)));
+    }
+    if let Some(number) = token.downcast_ref::<Real>() {
+        return Ok(Some(Value::Number(number.value())));
+    }
+    if let Some(number) = token.downcast_ref::<Rational>() {
+        return Ok(Some(Value::Number(number.value())));
+    }
+    if let Some(constant) = token.downcast_ref::<MathConst>() {
+        return Ok(Some(Value::Number(constant.value())));
+    }
+    if let Some(constant) = token.downcast_ref::<BoolConst>() {
+        return Ok(Some(Value::Bool(*constant == BoolConst::True)));
+    }
+    if let Some(constant) = token.downcast_ref::<PhysicConst>() {
+        return Ok(Some(Value::Number(constant.value())));
+    }
+    let name = match variable_name(token) {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+    env.get(&name)
+        .map(Some)
+        .ok_or(EvalError::UnboundVariable(name))
+}
+
+/// Value of a function applied to its arguments.
+fn function_value(token: &dyn Token, arguments: &[Value]) -> Result<Option<Value>, EvalError> {
+    let arguments = arguments
+        .iter()
+        .map(|argument| number_operand(token, *argument))
+        .collect::<Result<Vec<f64>, EvalError>>()?;
+    let value = match (token.downcast_ref::<AlgebraicFn>(), arguments.as_slice()) {
+        (Some(AlgebraicFn::Abs), [x]) => Some(x.abs()),
+        (Some(AlgebraicFn::Floor), [x]) => Some(x.floor()),
+        (Some(AlgebraicFn::Ceil), [x]) => Some(x.ceil()),
+        (Some(AlgebraicFn::Gcd), [a, b]) => Some(gcd(*a, *b)),
+        (Some(AlgebraicFn::Lcm), [a, b]) => Some(match gcd(*a, *b) {
+            0.0 => 0.0,
+            gcd => (a * b / gcd).abs(),
+        }),
+        (Some(_), _) => None,
+        (None, [x]) => token
+            .downcast_ref::<GeometricFn>()
+            .map(|f| geometric(f, *x)),
+        (None, _) => None,
+    };
+    Ok(value.map(Value::Number))
+}
+
+/// Value of a trigonometric or hyperbolic function (or its inverse).
+fn geometric(function: &GeometricFn, x: f64) -> f64 {
+    match function {
+        GeometricFn::Sin => x.sin(),
+        GeometricFn::Cos => x.cos(),
+        GeometricFn::Tan => x.tan(),
+        GeometricFn::Cot => x.tan().recip(),
+        GeometricFn::Sec => x.cos().recip(),
+        GeometricFn::Csc => x.sin().recip(),
+        GeometricFn::ArcSin => x.asin(),
+        GeometricFn::ArcCos => x.acos(),
+        GeometricFn::ArcTan => x.atan(),
+        GeometricFn::ArcCot => x.recip().atan(),
+        GeometricFn::ArcSec => x.recip().acos(),
+        GeometricFn::ArcCsc => x.recip().asin(),
+        GeometricFn::Sinh => x.sinh(),
+        GeometricFn::Cosh => x.cosh(),
+        GeometricFn::Tanh => x.tanh(),
+        GeometricFn::Coth => x.tanh().recip(),
+        GeometricFn::Sech => x.cosh().recip(),
+        GeometricFn::Csch => x.sinh().recip(),
+        GeometricFn::ArSinh => x.asinh(),
+        GeometricFn::ArCosh => x.acosh(),
+        GeometricFn::ArTanh => x.atanh(),
+        GeometricFn::ArCoth => x.recip().atanh(),
+        GeometricFn::ArSech => x.recip().acosh(),
+        GeometricFn::ArCsch => x.recip().asinh(),
+    }
+}
+
+/// Greatest common divisor of two integers (`NaN` if any of them isn't one).
+fn gcd(a: f64, b: f64) -> f64 {
+    if a.fract() != 0.0 || b.fract() != 0.0 {
+        return f64::NAN;
+    }
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0.0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Value of a prefix or postfix operator (or a floor, ceiling or absolute value group) applied to `a`.
+fn unary_value(token: &dyn Token, a: Value) -> Result<Option<Value>, EvalError> {
+    if let Some(LogicOp::Not) = token.downcast_ref::<LogicOp>() {
+        return Ok(Some(Value::Bool(!bool_operand(token, a)?)));
+    }
+    if let Some(bracket) = token.downcast_ref::<Bracket>() {
+        let a = number_operand(token, a)?;
+        return Ok(match bracket {
+            Bracket::OpenFloor => Some(a.floor()),
+            Bracket::OpenCeil => Some(a.ceil()),
+            Bracket::Bar => Some(a.abs()),
+            _ => None,
+        }
+        .map(Value::Number));
+    }
+    let Some(op) = token.downcast_ref::<MathOp>() else {
+        return Ok(None);
+    };
+    let a = number_operand(token, a)?;
+    Ok(match op {
+        MathOp::Add => Some(a),
+        MathOp::Subtract => Some(-a),
+        MathOp::Root => Some(a.sqrt()),
+        MathOp::Factorial => Some(factorial(a)),
+        _ => None,
+    }
+    .map(Value::Number))
+}
+
+/// Value of a binary operator applied to `a` and `b`.
+fn binary_value(token: &dyn Token, a: Value, b: Value) -> Result<Option<Value>, EvalError> {
+    if let Some(op) = token.downcast_ref::<LogicOp>() {
+        let (a, b) = (bool_operand(token, a)?, bool_operand(token, b)?);
+        return Ok(match op {
+            LogicOp::And => Some(a && b),
+            LogicOp::Or => Some(a || b),
+            LogicOp::XOr => Some(a != b),
+            LogicOp::XNOr | LogicOp::Iff => Some(a == b),
+            LogicOp::NAnd => Some(!(a && b)),
+            LogicOp::NOr => Some(!(a || b)),
+            LogicOp::Implies => Some(!a || b),
+            LogicOp::ConverseImplies => Some(a || !b),
+            LogicOp::NotImplies => Some(a && !b),
+            LogicOp::Not => None,
+        }
+        .map(Value::Bool));
+    }
+    if let Some(op) = token.downcast_ref::<RelationalOp>() {
+        // * two integers are compared exactly, anything else numeric as floats
+        let ordering = match (a, b) {
+            (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(&b)),
+            (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(&b)),
+            _ => a
+                .as_number()
+                .zip(b.as_number())
+                .and_then(|(a, b)| a.partial_cmp(&b)),
+        };
+        return Ok(Some(Value::Bool(match op {
+            RelationalOp::Equal => ordering.is_some_and(Ordering::is_eq),
+            RelationalOp::NotEqual => !ordering.is_some_and(Ordering::is_eq),
+            _ => {
+                number_operand(token, a)?;
+                number_operand(token, b)?;
+                match op {
+                    RelationalOp::LessThan => ordering.is_some_and(Ordering::is_lt),
+                    RelationalOp::LessThanOrEqual => ordering.is_some_and(Ordering::is_le),
+                    RelationalOp::GreaterThan => ordering.is_some_and(Ordering::is_gt),
+                    _ => ordering.is_some_and(Ordering::is_ge),
+                }
+            }
+        })));
+    }
+    if let Some(op) = token.downcast_ref::<MathOp>() {
+        let (a, b) = (number_operand(token, a)?, number_operand(token, b)?);
+        return Ok(match op {
+            MathOp::Add => Some(a + b),
+            MathOp::Subtract => Some(a - b),
+            MathOp::Multiply => Some(a * b),
+            MathOp::Divide => Some(a / b),
+            MathOp::Modulo => Some(a % b),
+            MathOp::Power => Some(a.powf(b)),
+            MathOp::Root | MathOp::Factorial => None,
+        }
+        .map(Value::Number));
+    }
+    Ok(None)
+}
+
+/// The largest natural number whose factorial is a finite float (`170!` is about `7.3e306`).
+const MAX_FINITE_FACTORIAL: f64 = 170.0;
+
+/// Factorial of a natural number (`NaN` for anything else).
+fn factorial(n: f64) -> f64 {
+    match n >= 0.0 && n.fract() == 0.0 {
+        // * past 170 the product overflows anyway, so it isn't worth computing
+        true if n > MAX_FINITE_FACTORIAL => f64::INFINITY,
+        true => (1..=n as u64).map(|i| i as f64).product(),
+        false => f64::NAN,
+    }
+}
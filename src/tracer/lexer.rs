@@ -4,21 +4,238 @@ use std::iter::Peekable;
 use std::str::CharIndices;
 // use log::debug;
 
+use crate::tracer::dialect::Dialect;
 use crate::tracer::tokens::*;
 
+/// Reads pieces of text as tokens.
+///
+/// Recognizers are usually zero-sized types generated with [`impl_lexer_token_from!`](crate::impl_lexer_token_from),
+/// but they can also be values built at runtime, like a [`TokenRegistry`](crate::registry::TokenRegistry).
 pub trait TokenRecognizer {
-    fn recognize_token<S: Into<String>>(input: S) -> Option<Box<dyn Token>>;
+    /// Attempts to read the input as a token (the first token type that accepts it wins).
+    fn recognize_token<S: Into<String>>(&self, input: S) -> Option<Box<dyn Token>> {
+        self.recognize_all(input).into_iter().next()
+    }
+
+    /// Returns every token the input can be read as, in priority order.
+    fn recognize_all<S: Into<String>>(&self, input: S) -> Vec<Box<dyn Token>>;
+
+    /// Returns the trie of every symbol this recognizer can read.
+    fn trie(&self) -> &SymbolTrie;
 }
 
+impl<R: TokenRecognizer> TokenRecognizer for &R {
+    fn recognize_token<S: Into<String>>(&self, input: S) -> Option<Box<dyn Token>> {
+        (**self).recognize_token(input)
+    }
+
+    fn recognize_all<S: Into<String>>(&self, input: S) -> Vec<Box<dyn Token>> {
+        (**self).recognize_all(input)
+    }
+
+    fn trie(&self) -> &SymbolTrie {
+        (**self).trie()
+    }
+}
+
+/// A prefix tree with the fixed spellings of a set of token types.
+///
+/// It's used by the [`Lexer`] to find the longest symbol at the current position in a single
+/// pass over the input, instead of trying every spelling one by one.
+#[derive(Debug, Clone)]
+pub struct SymbolTrie {
+    nodes: Vec<TrieNode>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: Vec<(char, usize)>,
+    terminal: bool,
+}
+
+impl Default for SymbolTrie {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SymbolTrie {
+    /// Creates an empty trie.
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![TrieNode::default()],
+        }
+    }
+
+    /// Creates a trie that contains all the given symbols.
+    pub fn from_symbols<'a>(symbols: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut trie = Self::new();
+        symbols.into_iter().for_each(|symbol| trie.insert(symbol));
+        trie
+    }
+
+    /// Adds a symbol to the trie.
+    pub fn insert(&mut self, symbol: &str) {
+        let mut node = 0;
+        for c in symbol.chars() {
+            node = match self.child(node, c) {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(TrieNode::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.push((c, child));
+                    child
+                }
+            };
+        }
+        self.nodes[node].terminal = true;
+    }
+
+    /// Returns the byte length of every symbol that is a prefix of `input`, shortest first.
+    pub fn prefix_lengths(&self, input: &str) -> Vec<usize> {
+        let mut lengths = Vec::new();
+        let mut node = 0;
+        for (i, c) in input.char_indices() {
+            match self.child(node, c) {
+                Some(child) => node = child,
+                None => break,
+            }
+            if self.nodes[node].terminal {
+                lengths.push(i + c.len_utf8());
+            }
+        }
+        lengths
+    }
+
+    /// Returns true if the trie contains exactly this symbol.
+    pub fn contains(&self, symbol: &str) -> bool {
+        self.prefix_lengths(symbol).last() == Some(&symbol.len())
+    }
+
+    fn child(&self, node: usize, c: char) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .find(|(edge, _)| *edge == c)
+            .map(|(_, child)| *child)
+    }
+}
+
+/// A region of the source code that a token was read from.
+///
+/// `start` and `end` are byte offsets into the original (unstripped) source, so
+/// `&src[span.start..span.end]` is the exact text of the token.
+/// `line` and `column` are 1-based and point at the first character of the token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    /// Returns the byte range of the span.
+    pub fn range(&self) -> std::ops::Range<usize> {
+        self.start..self.end
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// A value (usually a token) together with the [`Span`] of source it was read from.
+///
+/// Dereferences to the inner value, so a `Spanned<Box<dyn Token>>` can be used as a token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Span,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(value: T, span: Span) -> Self {
+        Self { value, span }
+    }
+
+    /// Discards the span and returns the inner value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> std::ops::Deref for Spanned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// The kind of problem found while lexing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LexErrorKind {
+    /// The text doesn't match any token known by the lexer.
+    UnknownSymbol,
+    /// A `\` that isn't followed by a command name.
+    UnterminatedCommand,
+    /// A numeric literal that is malformed or not accepted by the lexer (e.g. `1.2.3`).
+    InvalidNumber,
+}
+
+/// An error found while lexing, with the offending text and where it was found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexError {
+    pub kind: LexErrorKind,
+    pub lexeme: String,
+    pub span: Span,
+}
+
+impl std::fmt::Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            LexErrorKind::UnknownSymbol => "unknown symbol",
+            LexErrorKind::UnterminatedCommand => "unterminated command",
+            LexErrorKind::InvalidNumber => "invalid number",
+        };
+        write!(f, "{kind} `{}` at {}", self.lexeme, self.span)
+    }
+}
+
+impl std::error::Error for LexError {}
+
 #[derive(Debug, Clone)]
 /// A lexer for tokenizing source code.
-pub struct Lexer<T: TokenRecognizer> {
-    src_code: String,
-    char_indices: Peekable<CharIndices<'static>>,
-    _marker: std::marker::PhantomData<T>,
+///
+/// The lexer borrows the source code for its whole lifetime (`'src`), so no copy of it is made.
+pub struct Lexer<'src, T: TokenRecognizer> {
+    src_code: &'src str,
+    char_indices: Peekable<CharIndices<'src>>,
+    line: usize,
+    column: usize,
+    dialect: Dialect,
+    multi_letter_names: bool,
+    bool_letters: bool,
+    rationals: bool,
+    recognizer: T,
+    /// Precedence the last token takes the next operand at (`21` after `*`, `0` after `(`).
+    ///
+    /// A literal that reads like an operation (`3+4i`, `3/4`) isn't formed if the operator before it
+    /// binds at least as tight as that operation: `2*3+4i` is `2 * 3 + 4i`.
+    operand_precedence: u8,
+    /// Whether the next token is an operand (at the start, after an operator or an opening bracket).
+    ///
+    /// Where `|` can be both OR and a bar, it's a bar when it opens an operand (`|x| * 2`)
+    /// or closes one of the [`Self::open_bars`], and OR otherwise (`A | B`).
+    expects_operand: bool,
+    /// Number of absolute value bars opened and not closed yet.
+    open_bars: usize,
 }
 
-impl<T: TokenRecognizer> Lexer<T> {
+impl<'src, T: TokenRecognizer + Default> Lexer<'src, T> {
     /// Creates a new `Lexer` instance with the given source code.
     ///
     /// # Arguments
@@ -28,121 +245,558 @@ impl<T: TokenRecognizer> Lexer<T> {
     /// # Example
     ///
     /// ```
-    /// use logic_tracer::Lexer;
-    /// use logic_tracer::tokens::Token;
-    /// use logic_tracer::tokens::numbers::*;
-    /// use logic_tracer::tokens::operators::*;
+    /// use logic_tracer::lexer::*;
     ///
     /// let code = String::from("25.1 * 42 - 13");
-    /// let lexer = Lexer::new(code);
+    /// let lexer = Lexer::<MathLexer>::new(&code);
     ///
-    /// for token in lexer {
-    ///     println!("{:?}", token);
+    /// for result in lexer {
+    ///     let token = result.expect("the source only contains math tokens");
+    ///     println!("{:?} at {}", token.value, token.span);
     /// }
     /// ```
-    pub fn new<S: Into<String>>(src_code: S) -> Self {
-        let src_str: String = src_code.into();
-
-        // Remove all whitespace and control characters from the source code
-        let trimmed_str: String = src_str
-            .chars()
-            .filter(|c| !c.is_whitespace()) // remove \t, \n, \r, \x20, etc.
-            .filter(|c| !c.is_ascii_control()) // remove \x00 - \x1F, \x7F, etc.
-            .collect();
-
-        println!(
-            "\nNew {}:\t {}",
-            std::any::type_name::<T>().split("::").last().unwrap(),
-            format!("\x1B[1m\x1B[3m{}\x1B[0m\n", src_str) // format!("\x1B[1m\x1B[3m{}\x1B[0m\n", trimmed_str)
-        );
+    pub fn new(src_code: &'src str) -> Self {
+        Self::with_recognizer(src_code, T::default())
+    }
+}
 
+impl<'src, T: TokenRecognizer> Lexer<'src, T> {
+    /// Creates a new `Lexer` that reads the source code with the given recognizer.
+    ///
+    /// This is the way to use a recognizer that holds some state, like a
+    /// [`TokenRegistry`](crate::registry::TokenRegistry) (which can be lent to many lexers).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use logic_tracer::lexer::*;
+    /// use logic_tracer::registry::TokenRegistry;
+    /// use logic_tracer::tokens::*;
+    ///
+    /// let mut registry = TokenRegistry::new().with::<LogicOp>().with::<AlphaUpper>();
+    /// registry.add_alias("ET", "&").unwrap();
+    ///
+    /// let tokens = Lexer::with_recognizer("A ET B", &registry).tokenize().unwrap();
+    /// assert_eq!(format!("{:?}", tokens[1].value), "And");
+    /// ```
+    pub fn with_recognizer(src_code: &'src str, recognizer: T) -> Self {
         Self {
-            src_code: trimmed_str.clone(),
-            char_indices: Box::leak(trimmed_str.into_boxed_str())
-                .char_indices()
-                .peekable(),
-            _marker: std::marker::PhantomData,
+            src_code,
+            char_indices: src_code.char_indices().peekable(),
+            line: 1,
+            column: 1,
+            operand_precedence: 0,
+            expects_operand: true,
+            open_bars: 0,
+            dialect: Dialect::default(),
+            multi_letter_names: false,
+            bool_letters: false,
+            rationals: false,
+            recognizer,
         }
     }
 
-    /// Generates a table of tokens by tokenizing the entire source code.
+    /// Sets the [`Dialect`] used to read the symbols that are ambiguous (like `^` or `e`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use logic_tracer::dialect::Dialect;
+    /// use logic_tracer::lexer::*;
+    ///
+    /// let tokens = Lexer::<LogicLexer>::new("A ^ B")
+    ///     .with_dialect(Dialect::Programmer)
+    ///     .get_token_table().unwrap();
+    /// assert_eq!(format!("{:?}", tokens[1].value), "XOr");
+    /// ```
+    pub fn with_dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Sets whether names longer than one letter (`enable`, `carry_in`) are read as a single
+    /// [`Identifier`] (when the recognizer accepts them).
+    ///
+    /// It's disabled by default, so `AB` is read as the two variables `A` and `B`.
+    /// Subscripted variables (`x_1`) are always read as a single identifier.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use logic_tracer::lexer::*;
+    ///
+    /// let src = "enable & carry";
+    /// assert_eq!(Lexer::<LogicLexer>::new(src).get_token_table().unwrap().len(), 12);
+    /// let tokens = Lexer::<LogicLexer>::new(src).with_multi_letter_names(true).get_token_table().unwrap();
+    /// assert_eq!(tokens.len(), 3);
+    /// ```
+    pub fn with_multi_letter_names(mut self, enabled: bool) -> Self {
+        self.multi_letter_names = enabled;
+        self
+    }
+
+    /// Sets whether the letters `T` and `F` are read as the [`BoolConst`]s true and false
+    /// (instead of variables).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use logic_tracer::lexer::*;
+    ///
+    /// let tokens = Lexer::<LogicLexer>::new("A | T").with_bool_letters(true).get_token_table().unwrap();
+    /// assert_eq!(format!("{:?}", tokens[2].value), "True");
+    /// ```
+    pub fn with_bool_letters(mut self, enabled: bool) -> Self {
+        self.bool_letters = enabled;
+        self
+    }
+
+    /// Sets whether a fraction written without spaces (`3/4`) is read as a single exact
+    /// [`Rational`] (when the recognizer accepts it) instead of a division.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use logic_tracer::lexer::*;
+    ///
+    /// assert_eq!(Lexer::<MathLexer>::new("3/4").get_token_table().unwrap().len(), 3);
+    /// assert_eq!(Lexer::<MathLexer>::new("3/4").with_rationals(true).get_token_table().unwrap().len(), 1);
+    /// ```
+    pub fn with_rationals(mut self, enabled: bool) -> Self {
+        self.rationals = enabled;
+        self
+    }
+
+    /// Returns the token the lexeme stands for in the lexer's dialect.
+    fn resolve(&self, lexeme: &str) -> Option<Box<dyn Token>> {
+        if let Some(constant) = BoolConst::from_letter(lexeme).filter(|_| self.bool_letters) {
+            return Some(Box::new(constant));
+        }
+        let mut candidates = self.recognizer.recognize_all(lexeme);
+        // * `=` is the loosest equivalence in logic (`A & 1 = A`), but an equality where comparisons are read
+        if lexeme == "=" && self.reads_comparisons() {
+            candidates.retain(|token| !token.is::<LogicOp>());
+        }
+        // * `|` is a bar where it opens or closes an absolute value, and OR anywhere else
+        let is_bar = |token: &dyn Token| token.downcast_ref::<Bracket>() == Some(&Bracket::Bar);
+        if candidates.len() > 1 && candidates.iter().any(|token| is_bar(token.as_ref())) {
+            let bar = self.expects_operand || self.open_bars > 0;
+            candidates.retain(|token| is_bar(token.as_ref()) == bar);
+        }
+        self.dialect.resolve(lexeme, candidates)
+    }
+
+    /// Tokenizes the entire source code, failing if any part of it can't be lexed.
     ///
-    /// This mehod resets the lexer and tokenizes the entire source code, returning a vector of boxed tokens
     /// # Returns
     ///
-    /// Returns a vector of boxed tokens that represent the tokenized input.
-    pub fn get_token_table(&mut self) -> Vec<Box<dyn Token>> {
-        let mut tokens: Vec<Box<dyn Token>> = Vec::new();
-        // * restart the lexer (reset the char_indices iterator)
-        self.char_indices = Box::leak(self.src_code.clone().into_boxed_str())
-            .char_indices()
-            .peekable();
-        while let Some(token) = self.next() {
-            // Tokenize the entire source code.
-            tokens.push(token); // Add the token to the table.
+    /// Returns every token of the source, or every [`LexError`] found in it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use logic_tracer::lexer::*;
+    ///
+    /// let errors = Lexer::<LogicLexer>::new("A & $ B").tokenize().unwrap_err();
+    /// assert_eq!(errors[0].kind, LexErrorKind::UnknownSymbol);
+    /// assert_eq!(errors[0].span.range(), 4..5);
+    /// ```
+    pub fn tokenize(&mut self) -> Result<Vec<Spanned<Box<dyn Token>>>, Vec<LexError>> {
+        match self.tokenize_recovering() {
+            (tokens, errors) if errors.is_empty() => Ok(tokens),
+            (_, errors) => Err(errors),
         }
-        tokens // Return the token table.
     }
-}
 
-impl<T: TokenRecognizer> Iterator for Lexer<T> {
-    type Item = Box<dyn Token + 'static>;
+    /// Tokenizes the entire source code in recovery mode.
+    ///
+    /// Every unrecognised piece of text is skipped and reported, and lexing continues right after it.
+    ///
+    /// # Returns
+    ///
+    /// Returns the tokens that could be lexed along with the errors that were skipped.
+    pub fn tokenize_recovering(&mut self) -> (Vec<Spanned<Box<dyn Token>>>, Vec<LexError>) {
+        // * restart the lexer (reset the char_indices iterator)
+        self.char_indices = self.src_code.char_indices().peekable();
+        (self.line, self.column) = (1, 1);
+        self.operand_precedence = 0;
+        (self.expects_operand, self.open_bars) = (true, 0);
 
-    /// Advances the lexer to the next token, parsing and returning it.
+        let mut tokens: Vec<Spanned<Box<dyn Token>>> = Vec::new();
+        let mut errors: Vec<LexError> = Vec::new();
+        for result in self.by_ref() {
+            match result {
+                Ok(token) => tokens.push(token),
+                Err(error) => errors.push(error),
+            }
+        }
+        (tokens, errors)
+    }
+
+    /// Generates a table of tokens by tokenizing the entire source code.
+    ///
+    /// This mehod resets the lexer and tokenizes the entire source code (see [`Lexer::tokenize`]);
+    /// use [`Lexer::tokenize_recovering`] to keep the tokens around the text that can't be lexed.
     ///
     /// # Returns
     ///
-    /// Returns Some(Box<dyn [`Token`]>) containing the next token if available, or `None` when no more tokens are available.
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut c_string: String = String::new();
-        let mut c_token: Option<Box<dyn Token>> = None;
+    /// Returns a vector of spanned tokens that represent the tokenized input,
+    /// or every [`LexError`] found in it.
+    pub fn get_token_table(&mut self) -> Result<Vec<Spanned<Box<dyn Token>>>, Vec<LexError>> {
+        self.tokenize()
+    }
 
-        while let Some((_, c)) = self.char_indices.next() {
-            c_string.push(c);
+    /// Returns the byte offset of the next character to be read.
+    fn offset(&mut self) -> usize {
+        match self.char_indices.peek() {
+            Some((i, _)) => *i,
+            None => self.src_code.len(),
+        }
+    }
 
-            // Attempt to match the current string as a token.
-            c_token = T::recognize_token(&c_string);
-            // If the current character is a single-character token, return it immediately.
-            if c == '+' || c == '-' {
-                return c_token;
-            }
+    /// Consumes the next character, keeping track of the current line and column.
+    fn bump(&mut self) -> Option<char> {
+        let (_, c) = self.char_indices.next()?;
+        match c {
+            '\n' => (self.line, self.column) = (self.line + 1, 1),
+            _ => self.column += 1,
+        }
+        Some(c)
+    }
 
-            // Check the next character for multi-character tokens. (any: `dyn Token`)
-            if let Some((_, next_char)) = self.char_indices.clone().peekable().peek().cloned() {
-                let next_string = format!("{}{}", c_string, next_char);
-                if let Some(token) = T::recognize_token(&next_string) {
-                    c_token = Some(token); // Return the multi-character token.
-                    continue; // Continue scanning for the next token.
-                }
+    /// Returns the `n`-th character after the current position, without consuming anything.
+    fn peek_nth(&mut self, n: usize) -> Option<char> {
+        self.char_indices.clone().nth(n).map(|(_, c)| c)
+    }
+
+    /// Consumes characters while they match the predicate.
+    fn eat_while(&mut self, predicate: impl Fn(char) -> bool) {
+        while self.peek_nth(0).is_some_and(&predicate) {
+            self.bump();
+        }
+    }
+
+    /// Consumes characters until the given byte offset is reached.
+    fn advance_to(&mut self, offset: usize) {
+        while self.offset() < offset {
+            self.bump();
+        }
+    }
+
+    /// Skips all whitespace and control characters (\t, \n, \r, \x20, \x00 - \x1F, \x7F, etc.).
+    fn skip_whitespace(&mut self) {
+        self.eat_while(|c| c.is_whitespace() || c.is_ascii_control());
+    }
+
+    /// Lexes a numeric literal: `digit+ ('.' digit+)? (('e' | 'E') ('+' | '-')? digit+)?`, an
+    /// imaginary (`2i`) or complex (`3+4i`) literal, a fraction (`3/4`, see [`Self::with_rationals`]),
+    /// or a number in another base (`0b1011`, `0o17`, `0xFF`, `16#1F`).
+    ///
+    /// Complex literals and fractions are only formed where the operator before them doesn't bind
+    /// their first part tighter (see `operand_precedence`).
+    fn lex_number(&mut self, start: usize) -> Result<Box<dyn Token>, LexErrorKind> {
+        if let Some(prefix) = radix_prefix_length(&self.src_code[start..]) {
+            self.advance_to(start + prefix);
+            self.eat_while(char::is_alphanumeric);
+            let end = self.offset();
+            return self
+                .resolve(&self.src_code[start..end])
+                .ok_or(LexErrorKind::InvalidNumber);
+        }
+
+        let rest = &self.src_code[start..];
+        let digits = digits_length(rest);
+        let mantissa = match fraction_length(&rest[digits..]) {
+            Some(fraction) => digits + fraction,
+            None => digits,
+        };
+        // * a second fractional part makes the whole literal invalid (e.g. `1.2.3`)
+        if fraction_length(&rest[mantissa..]).is_some() && mantissa > digits {
+            self.advance_to(start + mantissa);
+            self.eat_while(|c| c.is_ascii_digit() || c == '.');
+            return Err(LexErrorKind::InvalidNumber);
+        }
+        let real = mantissa + exponent_length(&rest[mantissa..]).unwrap_or(0);
+
+        // * the longest literal the recognizer accepts: `6.022e23`, `2i`, `3+4i` or `3/4`
+        let mut lengths = vec![mantissa, real];
+        lengths.extend(imaginary_length(rest));
+        if self.operand_precedence < self.infix_precedence(&MathOp::Add) {
+            lengths.extend(
+                ['+', '-']
+                    .iter()
+                    .filter_map(|&sign| rest[real..].strip_prefix(sign))
+                    .filter_map(imaginary_length)
+                    .map(|length| real + 1 + length),
+            );
+        }
+        if self.rationals
+            && mantissa == digits
+            && self.operand_precedence < self.infix_precedence(&MathOp::Divide)
+        {
+            lengths.extend(
+                rest[digits..]
+                    .strip_prefix('/')
+                    .map(digits_length)
+                    .filter(|&length| length > 0)
+                    .map(|length| digits + 1 + length),
+            );
+        }
+        lengths.sort_unstable();
+        lengths.dedup();
+        for length in lengths.into_iter().rev() {
+            if let Some(token) = self.resolve(&rest[..length]) {
+                self.advance_to(start + length);
+                return Ok(token);
             }
+        }
+        self.advance_to(start + mantissa);
+        Err(LexErrorKind::InvalidNumber)
+    }
+
+    /// Lexes a backslash command such as `\tau` (the command name is the longest run of letters).
+    fn lex_command(&mut self, start: usize) -> Result<Box<dyn Token>, LexErrorKind> {
+        self.bump(); // '\'
+        self.eat_while(char::is_alphabetic);
+        let end = self.offset();
+        let command = &self.src_code[start..end];
+        match command.len() {
+            1 => Err(LexErrorKind::UnterminatedCommand),
+            _ => self.resolve(command).ok_or(LexErrorKind::UnknownSymbol),
+        }
+    }
+
+    /// Returns the byte length of the identifier at the start of `rest`, if it starts with a letter.
+    ///
+    /// That's a letter with an optional subscript (`x_1`), or the whole name when multi-letter names
+    /// are enabled (`carry_in`).
+    fn identifier_length(&self, rest: &str) -> Option<usize> {
+        let first = rest.chars().next().filter(|c| c.is_alphabetic())?;
+        let name = &rest[first.len_utf8()..];
+        let name = match self.multi_letter_names {
+            true => name.trim_start_matches(|c: char| c.is_alphanumeric() || c == '_'),
+            false => name,
+        };
+        let mut length = rest.len() - name.len();
+        let subscript = rest[length..].strip_prefix('_').unwrap_or_default();
+        let digits = subscript.len()
+            - subscript
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .len();
+        if digits > 0 {
+            length += 1 + digits;
+        }
+        Some(rest[..length].trim_end_matches('_').len())
+    }
 
-            // Return the current token if a match is found or continue scanning.
-            match c_token {
-                Some(token) => return Some(token),
-                None => println!("Token not found for: {:?}", c_string),
-                // None => debug!("Token not found for: {:?}", c_string),
+    /// Reads the word at `start` as a word symbol (`and`, `MOD`), returning its length and token.
+    ///
+    /// The word is matched as written, and then in lowercase. A word that is part of a longer
+    /// name (`android`, `x_or`, `nor2`) is never a word symbol.
+    fn word_symbol(&self, start: usize) -> Option<(usize, Box<dyn Token>)> {
+        if self.src_code[..start].ends_with(is_name_char) {
+            return None;
+        }
+        let rest = &self.src_code[start..];
+        let length = rest.len() - rest.trim_start_matches(char::is_alphabetic).len();
+        let word = &rest[..length];
+        if !is_word(word) || rest[length..].starts_with(is_name_char) {
+            return None;
+        }
+        let lowercase = word.to_lowercase();
+        [word, lowercase.as_str()]
+            .into_iter()
+            .filter(|word| self.recognizer.trie().contains(word))
+            .find_map(|word| self.resolve(word))
+            .map(|token| (length, token))
+    }
+
+    /// Returns the precedence of a binary operator in the dialect of the lexer.
+    fn infix_precedence(&self, op: &MathOp) -> u8 {
+        let infix = self.dialect.notation(op).infix;
+        infix.map_or(0, |(precedence, _)| precedence)
+    }
+
+    /// Returns true if the recognizer reads `<` as a comparison.
+    fn reads_comparisons(&self) -> bool {
+        self.recognizer
+            .recognize_token("<")
+            .is_some_and(|token| token.is::<RelationalOp>())
+    }
+
+    /// Lexes the longest symbol (from the recognizer's [`SymbolTrie`]) or identifier found at the
+    /// current position.
+    ///
+    /// Word symbols (like `and`) are only read by [`Self::word_symbol`], so `android` is never
+    /// lexed as `and` followed by other letters.
+    fn lex_symbol(&mut self, start: usize) -> Result<Box<dyn Token>, LexErrorKind> {
+        if let Some((length, token)) = self.word_symbol(start) {
+            self.advance_to(start + length);
+            return Ok(token);
+        }
+        let rest = &self.src_code[start..];
+        let mut lengths = self.recognizer.trie().prefix_lengths(rest);
+        lengths.retain(|&length| !is_word(&rest[..length]));
+        // * where comparisons are read, `x<-1` is `x < -1` and not a converse implication (`←` still is)
+        if self.reads_comparisons() {
+            lengths.retain(|&length| &rest[..length] != "<-");
+        }
+        lengths.extend(self.identifier_length(rest));
+        lengths.sort_unstable();
+        lengths.dedup();
+        for length in lengths.into_iter().rev() {
+            if let Some(token) = self.resolve(&rest[..length]) {
+                self.advance_to(start + length);
+                return Ok(token);
             }
         }
+        self.bump(); // skip the unknown character
+        Err(LexErrorKind::UnknownSymbol)
+    }
+}
+
+/// Returns true if a symbol is a word (like `and` or `mod`): two or more letters.
+fn is_word(symbol: &str) -> bool {
+    symbol.chars().nth(1).is_some() && symbol.chars().all(char::is_alphabetic)
+}
+
+/// Returns true if the character can be part of a name (`carry_in`, `x1`).
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Returns the length of the run of decimal digits at the start of `rest`.
+fn digits_length(rest: &str) -> usize {
+    rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len()
+}
+
+/// Returns the length of the fractional part (`.5`) at the start of `rest`, if there is one.
+fn fraction_length(rest: &str) -> Option<usize> {
+    let digits = digits_length(rest.strip_prefix('.')?);
+    (digits > 0).then_some(1 + digits)
+}
+
+/// Returns the length of the exponent (`e23`, `E-3`) at the start of `rest`, if there is one.
+fn exponent_length(rest: &str) -> Option<usize> {
+    let exponent = rest.strip_prefix(['e', 'E'])?;
+    let sign = usize::from(exponent.starts_with(['+', '-']));
+    let digits = digits_length(&exponent[sign..]);
+    (digits > 0).then_some(1 + sign + digits)
+}
+
+/// Returns the length of an imaginary literal (`4i`, `2.5e3i`) at the start of `rest`.
+///
+/// The `i` can't be followed by a name (`2in` is `2` and `in`).
+fn imaginary_length(rest: &str) -> Option<usize> {
+    let mut length = digits_length(rest);
+    if length == 0 {
+        return None;
+    }
+    length += fraction_length(&rest[length..]).unwrap_or(0);
+    length += exponent_length(&rest[length..]).unwrap_or(0);
+    let tail = rest[length..].strip_prefix('i')?;
+    (!tail.starts_with(is_name_char)).then_some(length + 1)
+}
+
+/// Returns the length of the base prefix (`0x`, `16#`, ...) at the start of `rest`, if the prefix
+/// is followed by at least one digit.
+fn radix_prefix_length(rest: &str) -> Option<usize> {
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let prefix = match &rest[digits..] {
+        tail if tail.starts_with('#') => digits + 1,
+        tail if digits == 1
+            && rest.starts_with('0')
+            && tail.starts_with(['b', 'B', 'o', 'O', 'x', 'X']) =>
+        {
+            2
+        }
+        _ => return None,
+    };
+    rest[prefix..]
+        .starts_with(char::is_alphanumeric)
+        .then_some(prefix)
+}
+
+impl<T: TokenRecognizer> Iterator for Lexer<'_, T> {
+    type Item = Result<Spanned<Box<dyn Token + 'static>>, LexError>;
 
-        c_token
+    /// Advances the lexer to the next token, parsing and returning it.
+    ///
+    /// When some text can't be lexed, a [`LexError`] is returned and the offending text is skipped,
+    /// so the iteration can go on with the rest of the source.
+    ///
+    /// # Returns
+    ///
+    /// Returns Some([`Spanned`]<Box<dyn [`Token`]>>) containing the next token and its location if available,
+    /// `Some(Err(LexError))` for text that isn't a token, or `None` when no more tokens are available.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.skip_whitespace();
+        let (start, line, column) = (self.offset(), self.line, self.column);
+
+        let result = match self.peek_nth(0)? {
+            '0'..='9' => self.lex_number(start),
+            '\\' => self.lex_command(start),
+            _ => self.lex_symbol(start),
+        };
+
+        let span = Span {
+            start,
+            end: self.offset(),
+            line,
+            column,
+        };
+        if let Ok(token) = &result {
+            let notation = self.dialect.notation_of(token.as_ref()).unwrap_or_default();
+            let infix = notation.infix.map(|(precedence, _)| precedence);
+            self.operand_precedence = infix.max(notation.prefix).unwrap_or(0);
+            self.expects_operand = match token.downcast_ref::<Bracket>() {
+                Some(Bracket::Bar) if self.expects_operand => {
+                    self.open_bars += 1;
+                    true
+                }
+                Some(Bracket::Bar) => {
+                    self.open_bars = self.open_bars.saturating_sub(1);
+                    false
+                }
+                Some(bracket) => bracket.is_open(),
+                None => infix.or(notation.prefix).is_some() || token.is::<Separator>(),
+            };
+        }
+        Some(match result {
+            Ok(token) => Ok(Spanned::new(token, span)),
+            Err(kind) => Err(LexError {
+                kind,
+                lexeme: self.src_code[span.range()].to_string(),
+                span,
+            }),
+        })
     }
 }
 
 /// Macro to implement a token recognizer for a given set of token types.
 ///
 /// This macro generates a token recognizer struct that can be used to tokenize source code.
+/// The symbols of all the token types are compiled (once) into a [`SymbolTrie`], so the lexer
+/// always reads the longest symbol available, in time linear to the length of the input.
+/// When more than one type accepts the same text, the first type listed wins.
 ///
 /// # Example
 ///
 /// ```rust
-/// impl_lexer_token_from!(MathLexer;  // recognizer for math tokens
+/// use logic_tracer::impl_lexer_token_from;
+/// use logic_tracer::lexer::*;
+/// use logic_tracer::tokens::*;
+///
+/// impl_lexer_token_from!(MyMathLexer;  // recognizer for math tokens
 ///    MathOp,
 ///    Real,
 ///    // any other token types (dyn Token)...
 /// );
+/// ```
 #[macro_export]
 macro_rules! impl_lexer_token_from {
     ($name:ident; $( $token_type:ty ),+ $(,)? ) => {
+        #[derive(Debug, Clone, Copy, Default)]
         pub struct $name;
 
         impl TokenRecognizer for $name {
@@ -155,13 +809,32 @@ macro_rules! impl_lexer_token_from {
             /// # Returns
             ///
             /// Returns `Some(Box<dyn Token>)` if the string matches a token type; otherwise, returns `None`.
-            fn recognize_token<S: Into<String>>(string: S) -> Option<Box<dyn Token>> {
+            fn recognize_token<S: Into<String>>(&self, string: S) -> Option<Box<dyn Token>> {
                 let s = string.into();
                 $(if let Some(value) = <$token_type>::from_str(&s) {
                     return Some(Box::new(value));
                 })+
                 None
             }
+
+            fn recognize_all<S: Into<String>>(&self, string: S) -> Vec<Box<dyn Token>> {
+                let s = string.into();
+                let mut tokens: Vec<Box<dyn Token>> = Vec::new();
+                $(if let Some(value) = <$token_type>::from_str(&s) {
+                    tokens.push(Box::new(value));
+                })+
+                tokens
+            }
+
+            /// Returns the trie of the symbols of every token type (built once, on first use).
+            fn trie(&self) -> &$crate::lexer::SymbolTrie {
+                static TRIE: std::sync::OnceLock<$crate::lexer::SymbolTrie> = std::sync::OnceLock::new();
+                TRIE.get_or_init(|| {
+                    $crate::lexer::SymbolTrie::from_symbols(
+                        [$(<$token_type as $crate::tokens::Token>::symbols()),+].concat()
+                    )
+                })
+            }
         }
     };
 }
@@ -171,32 +844,69 @@ impl_lexer_token_from!(MathLexer;
     MathOp,
     Real,
     Natural,
+    Rational,
+    Imaginary,
+    Complex,
+    AlgebraicFn,
+    GeometricFn,
+    Bracket,
+    Separator,
 );
 
 impl_lexer_token_from!(LogicLexer;
     LogicOp,
+    BoolConst,
+    Natural,
+    Quantifier,  // before the variables: `forall` is a quantifier, not a name
+    Alphabet,
+    AlphaUpper,
+    Identifier,
+    Bracket,  // after LogicOp: `|` is OR unless it opens or closes a bar (see `Lexer`)
+    Separator,
+);
+
+impl_lexer_token_from!(BitLexer;
+    BitOp,  // before MathOp: `^` is XOR, not a power
+    MathOp,
+    RelationalOp,
     Natural,
     Alphabet,
     AlphaUpper,
+    Identifier,
+    Bracket,  // after BitOp: `|` is OR unless it opens or closes a bar (see `Lexer`)
 );
 
 impl_lexer_token_from!(PhysicLexer;
     PhysicConst,
     MathOp,
     Real,
+    Bracket,
 );
 
 impl_lexer_token_from!(CompleteLexer;
     MathOp,
     LogicOp,
-    // RelOp,
+    BitOp,  // after LogicOp: only the shifts, rotations and words are bitwise
+    RelationalOp,
     Natural,
     Integer,
     Real,
+    Rational,
+    Imaginary,
+    Complex,
+    AlgebraicFn,  // before the variables: `sin` is a function, not a name
+    GeometricFn,
+    BigOp,  // and `Σ` is a sum, not a Greek letter
+    Quantifier,
     GreekAlpha,
     GreekUpperAlpha,
     Alphabet,
     AlphaUpper,
+    Identifier,
     MathConst,
+    BoolConst,
     PhysicConst,
+    Bracket,
+    Separator,
+    Script,
 );
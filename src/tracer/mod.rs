@@ -38,6 +38,11 @@ pub mod tokens;
 /// of tokens that represent the lexical elements of the code.
 pub mod lexer;
 
+// * Token Registry
+/// This module provides the [`TokenRegistry`](registry::TokenRegistry), a token recognizer that
+/// can be built and changed at runtime (e.g. to add symbol aliases read from a config file).
+pub mod registry;
+
 // * Parser (Syntactic Analysis) -> AST
 /// This module provides the parser, which is responsible for syntactic and semantic
 /// analysis of the token stream produced by the lexer.
@@ -56,6 +61,21 @@ pub mod parser;
 
 pub mod ast; // + semantic analysis...
 
+// * Evaluation
+/// This module evaluates an AST for some values of its variables
+/// (see [`AST::evaluate`](ast::AST::evaluate)).
+pub mod eval;
+
+// * Printing
+/// This module prints an AST back into an infix expression, with only the parentheses
+/// the precedences of its operators need (see [`Printer`](printer::Printer)).
+pub mod printer;
+
+// * Dialects
+/// This module defines the notations ([`Dialect`](dialect::Dialect)s) that the lexer and parser
+/// can read, resolving the symbols that mean different things to different people (like `^`).
+pub mod dialect;
+
 // // * Proposition
 // /// This module is intended to implement a simple propositional logic language (to be implemented).
 // ///
@@ -12,18 +12,228 @@
 //!
 //! Contains the grammar for the Neo language defined primarily using the `Token` types. This grammar is
 //! critical for parsing as it dictates the syntactic structure of the language.
+//!
+//! ```ebnf
+//! Expression ::= Term (Operator Term)*;
+//! Term ::= (Neg)? Variable | Number | GroupedExpression | FunctionCall | Predicate | Product | Quantification | BigOperation;
+//! FunctionCall ::= Function '(' Expression (',' Expression)* ')';
+//! Predicate ::= Variable '(' Expression (',' Expression)* ')';  // an uppercase or declared Variable
+//! Product ::= Letter '(' Expression ')';  // a lowercase letter in arithmetic: `n(n + 1)` is `n * (n + 1)`
+//! Quantification ::= Quantifier Variable Expression | Quantifier '(' Variable ',' Expression ')';
+//! BigOperation ::= BigOp '_' '{' Variable '=' Expression '}' '^' Term Expression
+//!                | BigOp '(' Variable '=' Expression '..' Expression ',' Expression ')';
+//! GroupedExpression ::= '(' Expression ')' | '⌊' Expression '⌋' | '|' Expression '|' | ...;
+//! ```
+//!
+//! The `(Operator Term)*` sequence is folded using *precedence climbing*, so each operator binds
+//! according to its precedence and associativity (see [`Parser::parse`]). Comparisons bind below
+//! arithmetic and above logic, and a chain of them is read pairwise: `0 <= x < 10` is `0 <= x & x < 10`.
 
 #![allow(unused)]
 
-use crate::tracer::{lexer::*, tokens::*};
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Formatter};
+
+use crate::tracer::{ast::*, dialect::Dialect, lexer::*, tokens::*};
+
+pub use crate::tracer::tokens::Associativity;
+
+/// Errors that can occur while parsing a token stream.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// A token was found where it can't be placed by the grammar.
+    UnexpectedToken {
+        /// Position of the token in the token stream.
+        index: usize,
+        /// Location of the token in the source code.
+        span: Span,
+        /// Debug representation of the offending token.
+        token: String,
+    },
+    /// The token stream ended while an operand was still expected.
+    UnexpectedEnd,
+    /// A bracket isn't paired with the right one (see [`check_brackets`]).
+    Bracket(BracketError),
+    /// A function was called with the wrong number of arguments (e.g. `gcd(a)`).
+    WrongArity {
+        /// Location of the function name in the source code.
+        span: Span,
+        /// Debug representation of the function.
+        function: String,
+        expected: usize,
+        found: usize,
+    },
+    /// A quantifier or big operator binds a variable already bound by an enclosing one
+    /// (e.g. `∀x ∃x P(x)`).
+    ShadowedVariable {
+        /// Location of the variable in the binder.
+        span: Span,
+        variable: String,
+        /// What binds the variable already (`"quantifier"`, `"sum"` or `"product"`).
+        binder: &'static str,
+    },
+    /// A name that isn't a predicate is applied to arguments (e.g. `carry(x)`).
+    ///
+    /// Predicates start with an uppercase letter (`P(x)`) or are declared with
+    /// [`Parser::with_predicates`], and in arithmetic a single lowercase letter multiplies a group (`n(n + 1)`).
+    UndeclaredPredicate {
+        /// Location of the name in the source code.
+        span: Span,
+        name: String,
+    },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedToken { token, span, .. } => {
+                write!(f, "unexpected token {token} at {span}")
+            }
+            ParseError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ParseError::Bracket(error) => write!(f, "{error}"),
+            ParseError::WrongArity {
+                span,
+                function,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{function} at {span} takes {expected} argument(s) but {found} were given"
+            ),
+            ParseError::ShadowedVariable {
+                span,
+                variable,
+                binder,
+            } => write!(
+                f,
+                "`{variable}` at {span} is already bound by an enclosing {binder}"
+            ),
+            ParseError::UndeclaredPredicate { span, name } => write!(
+                f,
+                "`{name}` at {span} is applied to arguments but isn't a predicate (predicates start with an uppercase letter or are declared)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Errors reported by [`check_brackets`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum BracketError {
+    /// An opening bracket that is never closed.
+    Unclosed { bracket: Bracket, span: Span },
+    /// A closing bracket that doesn't close any group.
+    Unopened { bracket: Bracket, span: Span },
+    /// A group closed by the wrong bracket (e.g. `(A]`).
+    Mismatched {
+        /// The bracket that opened the group.
+        open: Bracket,
+        /// Location of the opening bracket.
+        open_span: Span,
+        /// The bracket found instead of the right closing one.
+        bracket: Bracket,
+        span: Span,
+    },
+}
+
+impl Display for BracketError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BracketError::Unclosed { bracket, span } => {
+                write!(f, "unclosed `{}` at {span}", bracket.symbol())
+            }
+            BracketError::Unopened { bracket, span } => {
+                write!(f, "unopened `{}` at {span}", bracket.symbol())
+            }
+            BracketError::Mismatched {
+                open,
+                open_span,
+                bracket,
+                span,
+            } => write!(
+                f,
+                "`{}` at {span} doesn't close the `{}` at {open_span}",
+                bracket.symbol(),
+                open.symbol()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BracketError {}
+
+/// Checks that every bracket of a token stream is paired with the right one.
+///
+/// Returns every unclosed, unopened or mismatched bracket found. A `|` closes the innermost
+/// group if it was opened by another `|`, and opens a new group otherwise.
+///
+/// # Example
+///
+/// ```
+/// use logic_tracer::lexer::*;
+/// use logic_tracer::parser::*;
+///
+/// let tokens = Lexer::<MathLexer>::new("(1 + ⌊2.5]").get_token_table().unwrap();
+/// let errors = check_brackets(&tokens).unwrap_err();
+/// assert_eq!(errors.len(), 2); // `]` doesn't close `⌊`, and `(` is never closed
+/// ```
+pub fn check_brackets<'a, I>(tokens: I) -> Result<(), Vec<BracketError>>
+where
+    I: IntoIterator<Item = &'a Spanned<Box<dyn Token>>>,
+{
+    let mut open: Vec<(Bracket, Span)> = Vec::new();
+    let mut errors = Vec::new();
+    for token in tokens {
+        let Some(bracket) = token.downcast_ref::<Bracket>().cloned() else {
+            continue;
+        };
+        let span = token.span;
+        let closes_top = open
+            .last()
+            .is_some_and(|(top, _)| top.closing() == Some(bracket.clone()));
+        if closes_top {
+            open.pop();
+        } else if bracket.is_open() {
+            open.push((bracket, span));
+        } else {
+            errors.push(match open.pop() {
+                Some((open, open_span)) => BracketError::Mismatched {
+                    open,
+                    open_span,
+                    bracket,
+                    span,
+                },
+                None => BracketError::Unopened { bracket, span },
+            });
+        }
+    }
+    errors.extend(
+        open.into_iter()
+            .map(|(bracket, span)| BracketError::Unclosed { bracket, span }),
+    );
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors),
+    }
+}
 
 /// Represents a parser for converting a sequence of tokens into an AST.
 ///
 /// The `Parser` holds a list of tokens and facilitates their conversion into an AST by
 /// applying the grammatical rules of the language.
 pub struct Parser {
-    tokens: Vec<Box<dyn Token>>,
-    // current: usize,
+    tokens: VecDeque<Spanned<Box<dyn Token>>>,
+    current: usize,
+    dialect: Dialect,
+    /// Variables bound by the quantifiers and big operators being parsed (along with what binds
+    /// them, see [`binder_name`]), innermost last.
+    bound: Vec<(String, &'static str)>,
+    /// Names declared as predicates besides the uppercase ones (see [`Parser::with_predicates`]).
+    predicates: Vec<String>,
+    /// Whether the stream is arithmetic (it has a math operator or a number), where a lowercase
+    /// letter before a group multiplies it instead of being an undeclared predicate.
+    arithmetic: bool,
 }
 
 impl Parser {
@@ -31,21 +241,367 @@ impl Parser {
     ///
     /// # Arguments
     ///
-    /// * `tokens` - A vector of boxed tokens (dynamically typed) to be parsed.
-    pub fn new(tokens: Vec<Box<dyn Token>>) -> Self {
+    /// * `tokens` - A vector of spanned tokens (dynamically typed) to be parsed.
+    pub fn new(tokens: Vec<Spanned<Box<dyn Token>>>) -> Self {
+        let arithmetic = tokens.iter().any(|token| {
+            matches!(
+                token.kind(),
+                TokenKind::Operator(OperatorKind::Math) | TokenKind::Number
+            )
+        });
         Self {
-            tokens,
-            // current: 0,
+            tokens: tokens.into(),
+            current: 0,
+            dialect: Dialect::default(),
+            bound: Vec::new(),
+            predicates: Vec::new(),
+            arithmetic,
+        }
+    }
+
+    /// Sets the [`Dialect`] that decides how tight the logic operators bind.
+    pub fn with_dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Declares names that are predicates even if they don't start with an uppercase letter
+    /// (so `p(x)` is applied to `x` instead of multiplying it).
+    pub fn with_predicates<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.predicates.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Parses the token stream into an [`AST`].
+    ///
+    /// Follows the `Expression ::= Term (Operator Term)*` rule, where the operator sequence is
+    /// folded by *precedence climbing*. An empty token stream yields an empty AST.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use logic_tracer::lexer::*;
+    /// use logic_tracer::parser::*;
+    ///
+    /// let tokens = Lexer::<MathLexer>::new("25.1 * 42 - 13").get_token_table().unwrap();
+    /// let ast = Parser::new(tokens).parse().unwrap();
+    /// assert!(!ast.is_empty());
+    /// ```
+    pub fn parse(&mut self) -> Result<AST, ParseError> {
+        if self.tokens.is_empty() {
+            return Ok(AST::new());
+        }
+        if let Err(errors) = check_brackets(&self.tokens) {
+            return Err(ParseError::Bracket(errors[0].clone()));
+        }
+        let root = self.parse_expression(0)?;
+        match self.tokens.front() {
+            Some(token) => Err(self.unexpected(token)),
+            None => Ok(AST::with_root(root)),
+        }
+    }
+
+    /// Parses an expression whose operators bind at least as tight as `min_precedence`.
+    fn parse_expression(&mut self, min_precedence: u8) -> Result<Node, ParseError> {
+        let mut lhs = self.parse_term()?;
+        // * right operand of the last comparison, shared with the next one of a chain (`0 <= x < 10`)
+        let mut chained: Option<Node> = None;
+
+        while let Some(token) = self.tokens.front() {
+            if ends_expression(token.as_ref()) {
+                break;
+            }
+            if let Some(precedence) = postfix_precedence(token.as_ref(), self.dialect) {
+                if precedence < min_precedence {
+                    break;
+                }
+                let op = self.advance()?;
+                lhs = Node::boxed(op, vec![lhs]);
+                continue;
+            }
+
+            let Some((precedence, associativity)) = infix_precedence(token.as_ref(), self.dialect)
+            else {
+                return Err(self.unexpected(token));
+            };
+            if precedence < min_precedence {
+                break;
+            }
+            let op = self.advance()?;
+            let next_min = match associativity {
+                Associativity::Right => precedence,
+                Associativity::Left | Associativity::NonAssociative => precedence + 1,
+            };
+            let rhs = self.parse_expression(next_min)?;
+            if op.downcast_ref::<RelationalOp>().is_none() {
+                chained = None;
+                lhs = Node::boxed(op, vec![lhs, rhs]);
+                continue;
+            }
+            // * `a < b < c` reads as `a < b & b < c`
+            lhs = match chained.replace(rhs.clone()) {
+                Some(shared) => {
+                    Node::branch(LogicOp::And, vec![lhs, Node::boxed(op, vec![shared, rhs])])
+                }
+                None => Node::boxed(op, vec![lhs, rhs]),
+            };
+        }
+        Ok(lhs)
+    }
+
+    /// Parses a single term: an operand or a grouped expression, optionally preceded by prefix operators.
+    fn parse_term(&mut self) -> Result<Node, ParseError> {
+        let Some(token) = self.tokens.front() else {
+            return Err(ParseError::UnexpectedEnd);
+        };
+
+        if let Some(close) = token.downcast_ref::<Bracket>().and_then(Bracket::closing) {
+            let bracket = self.advance()?;
+            let inner = self.parse_expression(0)?;
+            self.expect(&close)?;
+            return Ok(
+                match bracket
+                    .downcast_ref::<Bracket>()
+                    .is_some_and(Bracket::is_function)
+                {
+                    true => Node::boxed(bracket, vec![inner]),
+                    false => inner,
+                },
+            );
+        }
+        if token.downcast_ref::<BigOp>().is_some() {
+            return self.parse_big_op();
         }
+        if token.downcast_ref::<Quantifier>().is_some() {
+            return self.parse_quantifier();
+        }
+
+        if let Some(precedence) = prefix_precedence(token.as_ref(), self.dialect) {
+            let op = self.advance()?;
+            let operand = self.parse_expression(precedence)?;
+            return Ok(Node::boxed(op, vec![operand]));
+        }
+        if let Some(arity) = function_arity(token.as_ref()) {
+            return self.parse_call(arity);
+        }
+        if matches!(token.kind(), TokenKind::Operator(_) | TokenKind::Delimiter) {
+            return Err(self.unexpected(token));
+        }
+        let (name, span) = (variable_name(token.as_ref()), token.span);
+        let leaf = self.advance()?;
+        let applied = self
+            .tokens
+            .front()
+            .is_some_and(|token| token.downcast_ref::<Bracket>() == Some(&Bracket::OpenParen));
+        let Some(name) = name.filter(|_| applied) else {
+            return Ok(Node::boxed(leaf, vec![]));
+        };
+        // * a predicate applied to its arguments (`P(x, y)`)
+        if name.starts_with(char::is_uppercase) || self.predicates.contains(&name) {
+            self.advance()?;
+            return Ok(Node::boxed(leaf, self.parse_arguments()?));
+        }
+        // * a lowercase letter times a group (`n(n + 1)`), only in arithmetic
+        if self.arithmetic
+            && name
+                .split('_')
+                .next()
+                .is_some_and(|letter| letter.chars().count() == 1)
+        {
+            let group = self.parse_term()?;
+            return Ok(Node::branch(
+                MathOp::Multiply,
+                vec![Node::boxed(leaf, vec![]), group],
+            ));
+        }
+        Err(ParseError::UndeclaredPredicate { span, name })
+    }
+
+    /// Parses a function call, whose arguments become the children of the function node.
+    fn parse_call(&mut self, arity: usize) -> Result<Node, ParseError> {
+        let span = self.tokens.front().ok_or(ParseError::UnexpectedEnd)?.span;
+        let function = self.advance()?;
+        self.expect(&Bracket::OpenParen)?;
+        let arguments = self.parse_arguments()?;
+        if arguments.len() != arity {
+            return Err(ParseError::WrongArity {
+                span,
+                function: format!("{function:?}"),
+                expected: arity,
+                found: arguments.len(),
+            });
+        }
+        Ok(Node::boxed(function, arguments))
+    }
+
+    /// Parses the comma-separated arguments of a call, up to its closing parenthesis.
+    fn parse_arguments(&mut self) -> Result<Vec<Node>, ParseError> {
+        let mut arguments = vec![self.parse_expression(0)?];
+        while self.eat(&Separator::Comma) {
+            arguments.push(self.parse_expression(0)?);
+        }
+        self.expect(&Bracket::CloseParen)?;
+        Ok(arguments)
+    }
+
+    /// Parses a quantified formula in the `∀x body` or the `forall(x, body)` form.
+    ///
+    /// The children of the node are the bound variable and the body. The body of the first form
+    /// binds like a negation: `∀x P(x) → Q(x)` reads as `(∀x P(x)) → Q(x)`.
+    fn parse_quantifier(&mut self) -> Result<Node, ParseError> {
+        let quantifier = self.advance()?;
+        let call = self.eat(&Bracket::OpenParen);
+        let variable = self.bind_variable(binder_name(quantifier.as_ref()))?;
+        let body = match call {
+            true => {
+                self.expect(&Separator::Comma)?;
+                let body = self.parse_expression(0)?;
+                self.expect(&Bracket::CloseParen)?;
+                body
+            }
+            false => self.parse_expression(self.dialect.quantifier_body_precedence())?,
+        };
+        self.bound.pop();
+        Ok(Node::boxed(quantifier, vec![variable, body]))
+    }
+
+    /// Consumes the variable bound by a quantifier or a big operator and brings it into scope.
+    ///
+    /// Fails if the variable is already bound by an enclosing quantifier or big operator.
+    fn bind_variable(&mut self, binder: &'static str) -> Result<Node, ParseError> {
+        let token = self.tokens.front().ok_or(ParseError::UnexpectedEnd)?;
+        let Some(name) = variable_name(token.as_ref()) else {
+            return Err(self.unexpected(token));
+        };
+        if let Some((_, enclosing)) = self.bound.iter().find(|(bound, _)| *bound == name) {
+            return Err(ParseError::ShadowedVariable {
+                span: token.span,
+                variable: name,
+                binder: enclosing,
+            });
+        }
+        self.bound.push((name, binder));
+        Ok(Node::boxed(self.advance()?, vec![]))
+    }
+
+    /// Parses a big operator in the `Σ_{i=1}^{n} body` or the `sum(i = 1..n, body)` form.
+    ///
+    /// The children of the node are the bound variable, the lower and upper bounds and the body.
+    fn parse_big_op(&mut self) -> Result<Node, ParseError> {
+        let op = self.advance()?;
+        let binder = binder_name(op.as_ref());
+        if self.eat(&Bracket::OpenParen) {
+            let (variable, lower) = self.parse_binding(binder)?;
+            self.expect(&Separator::Range)?;
+            let upper = self.parse_expression(0)?;
+            self.expect(&Separator::Comma)?;
+            let body = self.parse_expression(0)?;
+            self.expect(&Bracket::CloseParen)?;
+            self.bound.pop();
+            return Ok(Node::boxed(op, vec![variable, lower, upper, body]));
+        }
+        self.expect(&Script::Subscript)?;
+        self.expect(&Bracket::OpenCurly)?;
+        let (variable, lower) = self.parse_binding(binder)?;
+        self.expect(&Bracket::CloseCurly)?;
+        self.expect(&MathOp::Power)?;
+        let upper = self.parse_term()?; // `^{n + 1}` or `^n`
+        let body = self.parse_expression(self.dialect.big_op_body_precedence())?;
+        self.bound.pop();
+        Ok(Node::boxed(op, vec![variable, lower, upper, body]))
+    }
+
+    /// Parses the `i = 1` binding of a big operator, returning the variable and its first value.
+    fn parse_binding(&mut self, binder: &'static str) -> Result<(Node, Node), ParseError> {
+        let variable = self.bind_variable(binder)?;
+        self.expect(&RelationalOp::Equal)?;
+        Ok((variable, self.parse_expression(0)?))
     }
 
-    // todo: Build the AST data structure or look for some way to represent the AST...
-    // todo: AST is a type of tree that represents the structure of the source code.
-    // todo: The main goal of the AST is to simplify the source code and make it easier to analyze.
-    // todo: It also contains the grammar of the source code. (rules to mark the source code as valid)
-    pub fn parse(&mut self) {
-        // let mut ast = AST::new();
-        // let mut current = 0;
-        // let mut current_token = self.tokens[current];
+    /// Consumes the next token of the stream.
+    fn advance(&mut self) -> Result<Box<dyn Token>, ParseError> {
+        let token = self.tokens.pop_front().ok_or(ParseError::UnexpectedEnd)?;
+        self.current += 1;
+        Ok(token.into_inner())
     }
+
+    /// Consumes the next token if it's `expected`, returning true if it was.
+    fn eat<T: Token + PartialEq + 'static>(&mut self, expected: &T) -> bool {
+        let found = self
+            .tokens
+            .front()
+            .is_some_and(|token| token.downcast_ref::<T>() == Some(expected));
+        if found {
+            self.tokens.pop_front();
+            self.current += 1;
+        }
+        found
+    }
+
+    /// Consumes the next token, failing if it isn't `expected`.
+    fn expect<T: Token + PartialEq + 'static>(&mut self, expected: &T) -> Result<(), ParseError> {
+        if self.eat(expected) {
+            return Ok(());
+        }
+        match self.tokens.front() {
+            Some(token) => Err(self.unexpected(token)),
+            None => Err(ParseError::UnexpectedEnd),
+        }
+    }
+
+    fn unexpected(&self, token: &Spanned<Box<dyn Token>>) -> ParseError {
+        ParseError::UnexpectedToken {
+            index: self.current,
+            span: token.span,
+            token: format!("{:?}", token.value),
+        }
+    }
+}
+
+// * Operator tables: the precedences are declared with the operators (see `OperatorNotation`)
+
+/// Returns what a binder token is called in error messages (`"quantifier"`, `"sum"` or `"product"`).
+fn binder_name(token: &dyn Token) -> &'static str {
+    match token.downcast_ref::<BigOp>() {
+        Some(BigOp::Sum) => "sum",
+        Some(BigOp::Product) => "product",
+        None => "quantifier",
+    }
+}
+
+/// Returns true if the token ends the current expression: it closes a group or separates arguments.
+fn ends_expression(token: &dyn Token) -> bool {
+    token
+        .downcast_ref::<Bracket>()
+        .is_some_and(Bracket::is_close)
+        || token.downcast_ref::<Separator>().is_some()
+}
+
+/// Returns the number of arguments of a token used as a function.
+fn function_arity(token: &dyn Token) -> Option<usize> {
+    token
+        .downcast_ref::<AlgebraicFn>()
+        .map(AlgebraicFn::arity)
+        .or_else(|| token.downcast_ref::<GeometricFn>().map(GeometricFn::arity))
+}
+
+/// Precedence and associativity of a token used as a binary operator.
+fn infix_precedence(token: &dyn Token, dialect: Dialect) -> Option<(u8, Associativity)> {
+    dialect.notation_of(token)?.infix
+}
+
+/// Precedence of a token used as a prefix (unary) operator.
+///
+/// The operand of a prefix operator is parsed at this precedence, so `-2^2` reads as `-(2^2)`.
+fn prefix_precedence(token: &dyn Token, dialect: Dialect) -> Option<u8> {
+    dialect.notation_of(token)?.prefix
+}
+
+/// Precedence of a token used as a postfix (unary) operator.
+fn postfix_precedence(token: &dyn Token, dialect: Dialect) -> Option<u8> {
+    dialect.notation_of(token)?.postfix
 }
@@ -0,0 +1,172 @@
+//! Prints an [`AST`] back into an infix expression.
+//!
+//! The [`Printer`] only writes the parentheses that the precedences of the operators need,
+//! so parsing what it prints gives back the same tree.
+
+use crate::tracer::{ast::*, dialect::Dialect, tokens::*};
+
+/// Precedence of an expression that never needs parentheses (a leaf, a call or a group).
+const ATOM: u8 = u8::MAX;
+
+/// Prints ASTs as infix expressions with as few parentheses as possible,
+/// rendering their tokens in a [`Style`] (Unicode by default).
+///
+/// # Example
+///
+/// ```
+/// use logic_tracer::{lexer::*, parser::*, printer::*, tokens::Style};
+///
+/// let tokens = Lexer::<LogicLexer>::new("((A & B)) | (C & (D | E))").get_token_table().unwrap();
+/// let ast = Parser::new(tokens).parse().unwrap();
+/// assert_eq!(Printer::new().print(&ast), "A ∧ B ∨ C ∧ (D ∨ E)");
+/// assert_eq!(Printer::new().with_style(Style::Ascii).print(&ast), "A & B | C & (D | E)");
+/// assert_eq!(Printer::new().with_style(Style::Latex).print(&ast), "A \\land B \\lor C \\land (D \\lor E)");
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Printer {
+    dialect: Dialect,
+    style: Style,
+}
+
+impl Printer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the [`Dialect`] whose precedences decide where the parentheses go.
+    pub fn with_dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Sets the [`Style`] the tokens are rendered in.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Prints the tree (an empty tree prints as an empty string).
+    pub fn print(&self, ast: &AST) -> String {
+        ast.root
+            .as_ref()
+            .map(|root| self.print_node(root))
+            .unwrap_or_default()
+    }
+
+    /// Prints the subtree of a node.
+    pub fn print_node(&self, node: &Node) -> String {
+        self.write(node).0
+    }
+
+    /// Writes a node, returning its text and the precedence it binds at: an enclosing operator
+    /// that binds tighter needs parentheses around it.
+    fn write(&self, node: &Node) -> (String, u8) {
+        let token = node.token.as_ref();
+        let text = token.render(self.style);
+        let notation = self.dialect.notation_of(token).unwrap_or_default();
+        match (node.children.as_slice(), notation) {
+            ([], _) => {
+                // * a literal that reads like an operation binds like it:
+                // * a complex number like a sum (`2 × (3+4i)`), a fraction like a division (`(3/4)^2`)
+                // * and a negative number like a negation (`(-2)^2`)
+                let precedence = match () {
+                    _ if token.is::<Complex>() => self
+                        .dialect
+                        .notation(&MathOp::Add)
+                        .infix
+                        .map_or(ATOM, |(precedence, _)| precedence),
+                    _ if token.is::<Rational>() => self
+                        .dialect
+                        .notation(&MathOp::Divide)
+                        .infix
+                        .map_or(ATOM, |(precedence, _)| precedence),
+                    _ if text.starts_with('-') => self
+                        .dialect
+                        .notation(&MathOp::Subtract)
+                        .prefix
+                        .unwrap_or(ATOM),
+                    _ => ATOM,
+                };
+                (text, precedence)
+            }
+            (
+                [lhs, rhs],
+                Notation {
+                    infix: Some((precedence, associativity)),
+                    ..
+                },
+            ) => {
+                let lhs = self.operand(lhs, precedence, associativity != Associativity::Left);
+                let rhs = self.operand(rhs, precedence, associativity != Associativity::Right);
+                (format!("{lhs} {text} {rhs}"), precedence)
+            }
+            (
+                [operand],
+                Notation {
+                    prefix: Some(precedence),
+                    ..
+                },
+            ) => {
+                let operand = self.operand(operand, precedence, false);
+                (format!("{text}{}{operand}", spacing(&text)), precedence)
+            }
+            (
+                [operand],
+                Notation {
+                    postfix: Some(precedence),
+                    ..
+                },
+            ) => {
+                let operand = self.operand(operand, precedence, false);
+                (format!("{operand}{text}"), precedence)
+            }
+            ([variable, body], _) if token.is::<Quantifier>() => {
+                let variable = self.print_node(variable);
+                let precedence = self.dialect.quantifier_body_precedence();
+                let body = self.operand(body, precedence, false);
+                let text = format!("{text}{}{variable} {body}", spacing(&text));
+                (text, precedence)
+            }
+            ([variable, lower, upper, body], _) if token.is::<BigOp>() => {
+                let [variable, lower, upper, body] =
+                    [variable, lower, upper, body].map(|node| self.print_node(node));
+                (
+                    format!("{text}({variable} = {lower}..{upper}, {body})"),
+                    ATOM,
+                )
+            }
+            ([inner], _) if token.is::<Bracket>() => {
+                let close = token
+                    .downcast_ref::<Bracket>()
+                    .and_then(Bracket::closing)
+                    .map(|close| close.render(self.style))
+                    .unwrap_or_default();
+                (format!("{text}{}{close}", self.print_node(inner)), ATOM)
+            }
+            // * function calls and predicates (`gcd(a, b)`, `P(x)`)
+            (arguments, _) => {
+                let arguments: Vec<String> =
+                    arguments.iter().map(|arg| self.print_node(arg)).collect();
+                (format!("{text}({})", arguments.join(", ")), ATOM)
+            }
+        }
+    }
+
+    /// Writes the operand of an operator of the given precedence, in parentheses if it binds looser
+    /// (or as loose, if `strict`: the operand is on the side the operator doesn't associate to).
+    fn operand(&self, node: &Node, precedence: u8, strict: bool) -> String {
+        let (text, operand) = self.write(node);
+        match operand < precedence || (strict && operand == precedence) {
+            true => format!("({text})"),
+            false => text,
+        }
+    }
+}
+
+/// Returns the space that keeps a word operator apart from its operand (`not A` or `\\neg A`, but `¬A`).
+fn spacing(text: &str) -> &'static str {
+    match text.ends_with(char::is_alphanumeric) {
+        true => " ",
+        false => "",
+    }
+}
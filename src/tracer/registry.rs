@@ -0,0 +1,204 @@
+//! A token recognizer that is built (and can be changed) at runtime.
+//!
+//! The recognizers generated by [`impl_lexer_token_from!`](crate::impl_lexer_token_from) are fixed
+//! at compile time. A [`TokenRegistry`] holds the same information as a value: the token types
+//! (categories) it recognizes, in priority order, and any number of extra aliases for their symbols.
+//!
+//! # Example
+//!
+//! ```
+//! use logic_tracer::lexer::*;
+//! use logic_tracer::registry::TokenRegistry;
+//! use logic_tracer::tokens::*;
+//!
+//! let mut registry = TokenRegistry::new().with::<LogicOp>().with::<AlphaUpper>();
+//! registry.load_aliases("
+//!     ## logic operators written in French
+//!     ET = &
+//!     OU = |
+//!     /\\ = ∧
+//! ").unwrap();
+//!
+//! let tokens = Lexer::with_recognizer("A ET B /\\ C", &registry).tokenize().unwrap();
+//! assert_eq!(tokens.len(), 5);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+use crate::tracer::lexer::{SymbolTrie, TokenRecognizer};
+use crate::tracer::tokens::*;
+
+/// Errors that can occur while changing a [`TokenRegistry`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistryError {
+    /// The alias points to a symbol that none of the registered categories recognize.
+    UnknownSymbol(String),
+    /// A line of an alias configuration isn't of the form `alias = symbol`.
+    InvalidLine {
+        /// 1-based number of the line.
+        line: usize,
+        text: String,
+    },
+}
+
+impl Display for RegistryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::UnknownSymbol(symbol) => write!(f, "unknown symbol `{symbol}`"),
+            RegistryError::InvalidLine { line, text } => {
+                write!(f, "invalid alias at line {line}: `{text}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// A token type the registry can recognize.
+#[derive(Debug, Clone)]
+struct Category {
+    name: &'static str,
+    recognize: fn(&str) -> Option<Box<dyn Token>>,
+    symbols: &'static [&'static str],
+}
+
+/// Reads `input` as a `T` token.
+fn recognize<T: Token + 'static>(input: &str) -> Option<Box<dyn Token>> {
+    T::from_str(input).map(|token| Box::new(token) as Box<dyn Token>)
+}
+
+/// A set of token categories and symbol aliases that can be built and changed at runtime.
+///
+/// It implements [`TokenRecognizer`], so it can be used by a [`Lexer`](crate::lexer::Lexer)
+/// (see [`Lexer::with_recognizer`](crate::lexer::Lexer::with_recognizer)).
+#[derive(Debug, Clone, Default)]
+pub struct TokenRegistry {
+    categories: Vec<Category>,
+    aliases: HashMap<String, String>,
+    trie: SymbolTrie,
+}
+
+impl TokenRegistry {
+    /// Creates an empty registry (that recognizes nothing).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the token type `T` as the category with the lowest priority.
+    pub fn with<T: Token + 'static>(mut self) -> Self {
+        self.register::<T>();
+        self
+    }
+
+    /// Adds the token type `T` as the category with the lowest priority.
+    ///
+    /// Registering the same type twice has no effect.
+    pub fn register<T: Token + 'static>(&mut self) {
+        let name = std::any::type_name::<T>();
+        if self.categories.iter().any(|category| category.name == name) {
+            return;
+        }
+        self.categories.push(Category {
+            name,
+            recognize: recognize::<T>,
+            symbols: T::symbols(),
+        });
+        self.rebuild_trie();
+    }
+
+    /// Removes the token type `T` from the registry, returning true if it was registered.
+    pub fn unregister<T: Token + 'static>(&mut self) -> bool {
+        let name = std::any::type_name::<T>();
+        let len = self.categories.len();
+        self.categories.retain(|category| category.name != name);
+        self.rebuild_trie();
+        self.categories.len() != len
+    }
+
+    /// Makes `alias` read as the same token as `symbol` (e.g. `ET` as `&`).
+    ///
+    /// Returns an error if no registered category recognizes `symbol`.
+    pub fn add_alias<A: Into<String>, S: Into<String>>(
+        &mut self,
+        alias: A,
+        symbol: S,
+    ) -> Result<(), RegistryError> {
+        let symbol = self.canonical(&symbol.into()).to_string();
+        if self.recognize_all(symbol.as_str()).is_empty() {
+            return Err(RegistryError::UnknownSymbol(symbol));
+        }
+        self.aliases.insert(alias.into(), symbol);
+        self.rebuild_trie();
+        Ok(())
+    }
+
+    /// Removes an alias, returning the symbol it pointed to.
+    pub fn remove_alias(&mut self, alias: &str) -> Option<String> {
+        let symbol = self.aliases.remove(alias);
+        self.rebuild_trie();
+        symbol
+    }
+
+    /// Adds every alias of a configuration text, returning how many were added.
+    ///
+    /// Each line has the form `alias = symbol`. Blank lines and lines starting with `#` are ignored.
+    /// Nothing is added if any line is invalid.
+    pub fn load_aliases(&mut self, config: &str) -> Result<usize, RegistryError> {
+        let mut registry = self.clone();
+        let mut count = 0;
+        for (i, line) in config.lines().enumerate() {
+            let text = line.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            let invalid = || RegistryError::InvalidLine {
+                line: i + 1,
+                text: text.to_string(),
+            };
+            let (alias, symbol) = text.split_once('=').ok_or_else(invalid)?;
+            let (alias, symbol) = (alias.trim(), symbol.trim());
+            if alias.is_empty() || symbol.is_empty() {
+                return Err(invalid());
+            }
+            registry.add_alias(alias, symbol)?;
+            count += 1;
+        }
+        *self = registry;
+        Ok(count)
+    }
+
+    /// Returns the aliases of the registry and the symbols they point to.
+    pub fn aliases(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.aliases.iter().map(|(a, s)| (a.as_str(), s.as_str()))
+    }
+
+    /// Returns the symbol an alias points to (or the input itself if it isn't an alias).
+    fn canonical<'a>(&'a self, input: &'a str) -> &'a str {
+        self.aliases.get(input).map(String::as_str).unwrap_or(input)
+    }
+
+    fn rebuild_trie(&mut self) {
+        let symbols = self
+            .categories
+            .iter()
+            .flat_map(|c| c.symbols.iter().copied());
+        self.trie =
+            SymbolTrie::from_symbols(symbols.chain(self.aliases.keys().map(String::as_str)));
+    }
+}
+
+impl TokenRecognizer for TokenRegistry {
+    fn recognize_all<S: Into<String>>(&self, input: S) -> Vec<Box<dyn Token>> {
+        let input = input.into();
+        let input = self.canonical(&input);
+        self.categories
+            .iter()
+            .filter_map(|category| (category.recognize)(input))
+            .collect()
+    }
+
+    fn trie(&self) -> &SymbolTrie {
+        &self.trie
+    }
+}
@@ -0,0 +1,75 @@
+// Handle all the Token types that group or separate the parts of an expression (brackets, etc...)
+
+use super::*;
+use crate::impl_enum_token;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delimiter;
+
+pub trait DelimiterTrait: Token {
+    // * Add properties and methods for the delimiter type...
+}
+
+impl_enum_token!(Delimiter; DelimiterTrait;
+    // * the brackets of the `GroupedExpression` rule
+    Bracket (
+        OpenParen => ("("),
+        CloseParen => (")"),
+        OpenSquare => ("["),
+        CloseSquare => ("]"),
+        OpenCurly => ("{"),
+        CloseCurly => ("}"),
+        OpenCeil => ("⌈", "\\lceil"),
+        CloseCeil => ("⌉", "\\rceil"),
+        OpenFloor => ("⌊", "\\lfloor"),
+        CloseFloor => ("⌋", "\\rfloor"),
+        OpenDoubleSquare => ("⟦", "\\llbracket"),
+        CloseDoubleSquare => ("⟧", "\\rrbracket"),
+        OpenAngle => ("⟨", "\\langle"),
+        CloseAngle => ("⟩", "\\rangle"),
+        Bar => ("|"),  // absolute value: opens and closes the group
+    ),
+    // * the separators between the arguments of a function (and the bounds of a range)
+    Separator (
+        Comma => (","),
+        Range => (".."),
+    ),
+    // * the marker of the bounds of a big operator (`Σ_{i=1}^{n}`)
+    Script (
+        Subscript => ("_"),
+    ),
+);
+
+impl Bracket {
+    /// Returns true if the bracket opens a group.
+    pub fn is_open(&self) -> bool {
+        self.closing().is_some()
+    }
+
+    /// Returns true if the bracket closes a group.
+    pub fn is_close(&self) -> bool {
+        matches!(self, Bracket::Bar) || self.closing().is_none()
+    }
+
+    /// Returns the bracket that closes a group opened by this one (`None` for closing brackets).
+    pub fn closing(&self) -> Option<Bracket> {
+        match self {
+            Bracket::OpenParen => Some(Bracket::CloseParen),
+            Bracket::OpenSquare => Some(Bracket::CloseSquare),
+            Bracket::OpenCurly => Some(Bracket::CloseCurly),
+            Bracket::OpenCeil => Some(Bracket::CloseCeil),
+            Bracket::OpenFloor => Some(Bracket::CloseFloor),
+            Bracket::OpenDoubleSquare => Some(Bracket::CloseDoubleSquare),
+            Bracket::OpenAngle => Some(Bracket::CloseAngle),
+            Bracket::Bar => Some(Bracket::Bar),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the group changes the value of its content (floor, ceiling and absolute value).
+    ///
+    /// The other brackets only group: `(A)` is the same as `A`.
+    pub fn is_function(&self) -> bool {
+        matches!(self, Bracket::OpenCeil | Bracket::OpenFloor | Bracket::Bar)
+    }
+}
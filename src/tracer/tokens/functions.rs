@@ -0,0 +1,75 @@
+// Handle all the Token types that are functions of the `AdvancedOperation` rule (sin, gcd, etc...)
+
+use super::*;
+use crate::impl_enum_token;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function;
+
+pub trait FunctionTrait: Token {
+    // * Add properties and methods for the function type...
+}
+
+impl_enum_token!(Function; FunctionTrait;
+    AlgebraicFn (
+        Abs => ("abs", "\\abs"),
+        Floor => ("floor", "\\floor"),
+        Ceil => ("ceil", "\\ceil"),
+        Gcd => ("gcd", "\\gcd"),
+        Lcm => ("lcm", "\\lcm"),
+    ),
+    // * trigonometric and hyperbolic functions (and their inverses)
+    GeometricFn (
+        Sin => ("sin", "\\sin"),
+        Cos => ("cos", "\\cos"),
+        Tan => ("tan", "\\tan"),
+        Cot => ("cot", "\\cot"),
+        Sec => ("sec", "\\sec"),
+        Csc => ("csc", "\\csc"),
+        ArcSin => ("arcsin", "\\arcsin"),
+        ArcCos => ("arccos", "\\arccos"),
+        ArcTan => ("arctan", "\\arctan"),
+        ArcCot => ("arccot", "\\arccot"),
+        ArcSec => ("arcsec", "\\arcsec"),
+        ArcCsc => ("arccsc", "\\arccsc"),
+        Sinh => ("sinh", "\\sinh"),
+        Cosh => ("cosh", "\\cosh"),
+        Tanh => ("tanh", "\\tanh"),
+        Coth => ("coth", "\\coth"),
+        Sech => ("sech", "\\sech"),
+        Csch => ("csch", "\\csch"),
+        ArSinh => ("arsinh", "\\arsinh"),
+        ArCosh => ("arcosh", "\\arcosh"),
+        ArTanh => ("artanh", "\\artanh"),
+        ArCoth => ("arcoth", "\\arcoth"),
+        ArSech => ("arsech", "\\arsech"),
+        ArCsch => ("arcsch", "\\arcsch"),
+    ),
+    // * operators over a range of a bound variable (`Σ_{i=1}^{n} i`, `sum(i = 1..n, i)`)
+    BigOp (
+        Sum => ("Σ", "∑", "sum", "\\sum"),
+        Product => ("Π", "∏", "prod", "\\prod"),
+    ),
+    // * quantifiers of first-order logic over a bound variable (`∀x P(x)`, `exists(x, P(x))`)
+    Quantifier (
+        ForAll => ("∀", "forall", "\\forall"),
+        Exists => ("∃", "exists", "\\exists"),
+    ),
+);
+
+impl AlgebraicFn {
+    /// Returns the number of arguments the function takes.
+    pub fn arity(&self) -> usize {
+        match self {
+            AlgebraicFn::Gcd | AlgebraicFn::Lcm => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl GeometricFn {
+    /// Returns the number of arguments the function takes.
+    pub fn arity(&self) -> usize {
+        1
+    }
+}
@@ -12,30 +12,141 @@
 // Consider re-enabling and documenting the `variables` module if relevant for future extensions.
 // pub mod variables;  // Defines variable-related tokens like identifiers.
 
+pub mod delimiters; // Contains definitions for brackets and separators.
+pub mod functions; // Contains definitions for functions (sin, gcd, ...).
 pub mod numbers; // Contains definitions for numeric types.
 pub mod operators; // Contains definitions for various operators.
 pub mod variables; // Contains definitions for variable-related tokens.
 
+pub use delimiters::*;
+pub use functions::*;
 pub use numbers::*;
 pub use operators::*;
 pub use variables::*; // variables generator (constants, alphabets, hiragana, etc...)
 
+use std::any::{Any, TypeId};
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+
+/// Upcasts a token into a [`&dyn Any`](std::any::Any).
+///
+/// This is a supertrait of [`Token`] so that a `&dyn Token` can be inspected for its concrete type
+/// (see [`downcast_ref`](trait.Token.html#method.downcast_ref)).
+pub trait AsAny {
+    /// Returns the token as a `&dyn Any`, to be downcast to its concrete type.
+    fn as_any(&self) -> &dyn Any;
+}
+
+// * only for tokens: a `Box<dyn Token>` must upcast the token it holds, not itself
+impl<T: Token + Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Clones, compares and hashes a token behind a `Box<dyn Token>`.
+///
+/// This is a supertrait of [`Token`], implemented for every token type that is [`Clone`], [`Eq`]
+/// and [`Hash`]. Two tokens are only equal if they are of the same type.
+#[doc(hidden)]
+pub trait DynToken {
+    fn clone_box(&self) -> Box<dyn Token>;
+    fn dyn_eq(&self, other: &dyn Token) -> bool;
+    fn dyn_hash(&self, state: &mut dyn Hasher);
+}
+
+impl<T: Token + Clone + Eq + Hash + 'static> DynToken for T {
+    fn clone_box(&self) -> Box<dyn Token> {
+        Box::new(self.clone())
+    }
+
+    fn dyn_eq(&self, other: &dyn Token) -> bool {
+        other.downcast_ref::<T>() == Some(self)
+    }
+
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        TypeId::of::<T>().hash(&mut state);
+        self.hash(&mut state);
+    }
+}
+
+impl Clone for Box<dyn Token> {
+    fn clone(&self) -> Self {
+        (**self).clone_box()
+    }
+}
+
+impl PartialEq for dyn Token {
+    fn eq(&self, other: &Self) -> bool {
+        self.dyn_eq(other)
+    }
+}
+
+impl Eq for dyn Token {}
+
+// * lets `Box<dyn Token>`s be compared with `==` (and derived), see rust-lang/rust#31740
+impl PartialEq<&Self> for Box<dyn Token> {
+    fn eq(&self, other: &&Self) -> bool {
+        **self == ***other
+    }
+}
+
+impl Hash for dyn Token {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dyn_hash(state);
+    }
+}
 
 /// Represents a generic token within the language processing system.
 ///
 /// All token types must implement this trait to ensure they can be debugged and
 /// dynamically handled via polymorphism. Tokens are generally created from strings and
 /// can be represented back as strings for debugging and logging purposes.
+///
+/// Token types must also be [`Clone`], [`Eq`] and [`Hash`], so that a `Box<dyn Token>`
+/// (and the [`AST`](crate::ast::AST) holding it) can be cloned, compared and hashed.
 // * IN COMPUTER SCIENCE.
 // * A token is a string of one or more characters, which are treated as a single unit by a program.
 // * Tokens are the smallest elements of a program, and they are classified by the compiler according to their functionality.
-pub trait Token: Debug {
+pub trait Token: Debug + AsAny + DynToken {
     /// Constructs an instance of a token from a string, if possible.
     fn from_str<S: Into<String>>(string: S) -> Option<Self>
     where
         Self: Sized;
 
+    /// Returns every fixed spelling this token type can be read from (e.g. `"&"`, `"∧"`, `"\\tau"`).
+    ///
+    /// Token types that aren't read from a fixed table (like numbers) have no symbols.
+    fn symbols() -> &'static [&'static str]
+    where
+        Self: Sized,
+    {
+        &[]
+    }
+
+    /// Returns the token as it's written in an expression (e.g. `&`, `0xFF` or `x_1`).
+    ///
+    /// Token types defined outside of this crate are written as their debug representation
+    /// unless they override it.
+    fn text(&self) -> String {
+        format!("{self:?}")
+    }
+
+    /// Renders the token in the given [`Style`] (e.g. `∧`, `&`, `\\land` or `and`).
+    ///
+    /// Tokens without a spelling of their own in a style are rendered as their [`text`](Token::text).
+    fn render(&self, style: Style) -> String {
+        let _ = style;
+        self.text()
+    }
+
+    /// Returns the kind of the token (an operator, a number, a variable, ...).
+    ///
+    /// Token types defined outside of this crate are of [`TokenKind::Other`] unless they override it.
+    fn kind(&self) -> TokenKind {
+        TokenKind::of(self.as_any())
+    }
+
     /// Returns a string representation of the token, typically used for debugging.
     fn to_string(&self) -> String {
         let mut result = std::any::type_name::<Self>()
@@ -43,7 +154,7 @@ pub trait Token: Debug {
             .collect::<Vec<&str>>();
         result.reverse();
 
-        let mut token_type = result.get(0).unwrap();
+        let token_type = result.first().unwrap();
         let token_type = token_type
             .chars()
             .next()
@@ -64,6 +175,146 @@ pub trait Token: Debug {
     }
 }
 
+impl dyn Token + '_ {
+    /// Returns a reference to the concrete token if it is of type `T`.
+    ///
+    /// ```
+    /// use logic_tracer::tokens::*;
+    ///
+    /// let token: Box<dyn Token> = Box::new(LogicOp::And);
+    /// assert_eq!(token.downcast_ref::<LogicOp>(), Some(&LogicOp::And));
+    /// assert_eq!(token.downcast_ref::<MathOp>(), None);
+    /// ```
+    pub fn downcast_ref<T: Token + 'static>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+
+    /// Returns true if the concrete token is of type `T`.
+    pub fn is<T: Token + 'static>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+}
+
+/// The kind of a token, as returned by [`Token::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// An operator of the given family ([`MathOp`], [`LogicOp`], ...).
+    Operator(OperatorKind),
+    /// A numeric literal ([`Natural`], [`Real`], [`Complex`], ...).
+    Number,
+    /// A letter or a name ([`Alphabet`], [`GreekAlpha`], [`Identifier`], ...).
+    Variable,
+    /// A named value ([`MathConst`], [`BoolConst`], [`PhysicConst`]).
+    Constant,
+    /// A function applied to its arguments ([`AlgebraicFn`], [`GeometricFn`]).
+    Function,
+    /// An operator over a bound variable ([`BigOp`], [`Quantifier`]).
+    Binder,
+    /// A bracket, a separator or a script marker.
+    Delimiter,
+    /// A token type defined outside of this crate.
+    Other,
+}
+
+/// The family of an operator token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperatorKind {
+    Math,
+    Logic,
+    Relational,
+    Bitwise,
+}
+
+impl TokenKind {
+    /// Classifies a token by its concrete type.
+    fn of(token: &dyn Any) -> Self {
+        macro_rules! kind_of {
+            ($($kind:expr => $($type:ty),+;)+) => {
+                $(if $(token.is::<$type>())||+ {
+                    return $kind;
+                })+
+            };
+        }
+        kind_of!(
+            TokenKind::Operator(OperatorKind::Math) => MathOp;
+            TokenKind::Operator(OperatorKind::Logic) => LogicOp;
+            TokenKind::Operator(OperatorKind::Relational) => RelationalOp;
+            TokenKind::Operator(OperatorKind::Bitwise) => BitOp;
+            TokenKind::Number => Natural, Integer, Real, Rational, Imaginary, Complex;
+            TokenKind::Variable => Alphabet, AlphaUpper, GreekAlpha, GreekUpperAlpha, Identifier;
+            TokenKind::Constant => MathConst, BoolConst, PhysicConst;
+            TokenKind::Function => AlgebraicFn, GeometricFn;
+            TokenKind::Binder => BigOp, Quantifier;
+            TokenKind::Delimiter => Bracket, Separator, Script;
+        );
+        TokenKind::Other
+    }
+}
+
+/// A notation to render tokens in (see [`Token::render`]).
+///
+/// Every style but [`Name`](Style::Name) only renders spellings of the symbol tables,
+/// so the lexers can read the rendered text back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Style {
+    /// The canonical Unicode symbol (`∧`, `→`, `π`).
+    #[default]
+    Unicode,
+    /// Plain ASCII (`&`, `->`, `\\pi`), or Unicode for a symbol without an ASCII spelling (`Α`).
+    Ascii,
+    /// A LaTeX command (`\\land`, `\\implies`, `\\pi`).
+    Latex,
+    /// The spoken name (`and`, `implies`, `less than`), which isn't always a spelling of the table.
+    Name,
+}
+
+impl Style {
+    /// Picks the spelling of a variant of an `impl_enum_token!` table for this style.
+    ///
+    /// Spellings starting with `\\` are LaTeX, and ASCII words are names. When the table has no
+    /// spelling for the style, ASCII falls back to LaTeX and then to Unicode, LaTeX to ASCII and
+    /// then to Unicode, and the name comes from the variant (`LessThan` is `less than`).
+    #[doc(hidden)]
+    pub fn pick(self, spellings: &[&str], variant: &str) -> String {
+        let is_latex = |s: &&&str| s.starts_with('\\');
+        let is_ascii = |s: &&&str| s.is_ascii() && !s.starts_with('\\');
+        let is_word = |s: &&&str| s.chars().all(|c| c.is_ascii_alphabetic());
+        let find = |predicate: &dyn Fn(&&&str) -> bool| {
+            spellings.iter().find(predicate).map(|s| s.to_string())
+        };
+
+        let name = || {
+            find(&is_word).unwrap_or_else(|| {
+                let mut name = String::new();
+                let mut previous = ' ';
+                for c in variant.chars() {
+                    if c.is_uppercase() && previous.is_lowercase() {
+                        name.push(' ');
+                    }
+                    name.extend(c.to_lowercase());
+                    previous = c;
+                }
+                name
+            })
+        };
+        let unicode = || {
+            find(&|s| !s.is_ascii() && !is_latex(s))
+                .or_else(|| find(&|s| !is_latex(s)))
+                .unwrap_or_else(|| spellings[0].to_string())
+        };
+        match self {
+            Style::Unicode => unicode(),
+            Style::Ascii => find(&is_ascii)
+                .or_else(|| find(&is_latex))
+                .unwrap_or_else(unicode),
+            Style::Latex => find(&is_latex)
+                .or_else(|| find(&is_ascii))
+                .unwrap_or_else(unicode),
+            Style::Name => name(),
+        }
+    }
+}
+
 #[macro_export]
 /// Macro to implement specific token types for a given trait.
 ///
@@ -80,9 +331,9 @@ macro_rules! impl_enum_token {
             )
         ),+ $(,)?
     ) => {
-        crate::impl_token_trait!($token_type; $trait_name; $($name),+);
+        $crate::impl_token_trait!($token_type; $trait_name; $($name),+);
         $(
-            #[derive(Debug, Clone, PartialEq)]
+            #[derive(Debug, Clone, PartialEq, Eq, Hash)]
             pub enum $name { $($variant,)+ }
 
             impl Token for $name {
@@ -92,6 +343,29 @@ macro_rules! impl_enum_token {
                         _ => None
                     }
                 }
+
+                fn symbols() -> &'static [&'static str] {
+                    &[$($($str),+),+]
+                }
+
+                fn text(&self) -> String {
+                    self.symbol().to_string()
+                }
+
+                fn render(&self, style: $crate::tokens::Style) -> String {
+                    match self {
+                        $($name::$variant => style.pick(&[$($str),+], stringify!($variant)),)+
+                    }
+                }
+            }
+
+            impl $name {
+                /// Returns the main spelling of the token (the first one of its table).
+                pub fn symbol(&self) -> &'static str {
+                    match self {
+                        $($name::$variant => [$($str),+][0],)+
+                    }
+                }
             }
 
             impl $trait_name for $name {}  //
@@ -101,9 +375,11 @@ macro_rules! impl_enum_token {
 
 #[macro_export]
 /// Macro to implement specific token types for a given trait.
-/// Implements the 'from' method for the token type to create a Box<dyn Trait> from a string.
+/// Implements the `from` and `symbols` methods of the token category (e.g. `Operator::from`).
 ///
 /// This macro requires that al token types implement the specified trait and provides a `from_str` method to parse a string into a token.
+/// The token types are tried in the order they are listed, so the first one that reads a string wins
+/// (e.g. `^` is a [`MathOp`] before a [`LogicOp`] or a [`BitOp`]).
 macro_rules! impl_token_trait {
     ($token_type:ident; $trait_name:ident;
         $(
@@ -111,13 +387,27 @@ macro_rules! impl_token_trait {
         ),+ $(,)? // Supports multiple token types
     ) => {
         impl $token_type {
-            /// Attempts to create a boxed token from a string if it matches any of the specified types.
-            pub fn from<S: Into<String> + Copy>(string: S) -> Option<Box<dyn $trait_name>> {
-                $(if let Some(value) = $name::from_str(string) {
-                    Some(Box::new(value));  // Create some box with the token type
+            /// Creates a boxed token from a string, as the first of the token types of the category
+            /// (in the order they are declared) that reads it.
+            pub fn from<S: Into<String>>(string: S) -> Option<Box<dyn $trait_name>> {
+                let string = string.into();
+                $(if let Some(value) = $name::from_str(string.as_str()) {
+                    return Some(Box::new(value));
                 })+
                 None  // Return None if no types match
             }
+
+            /// Returns every fixed spelling a token of the category can be read from, without repeats,
+            /// in the order [`from`](Self::from) tries them.
+            pub fn symbols() -> Vec<&'static str> {
+                let mut symbols: Vec<&'static str> = Vec::new();
+                $(for symbol in <$name as $crate::tokens::Token>::symbols() {
+                    if !symbols.contains(symbol) {
+                        symbols.push(symbol);
+                    }
+                })+
+                symbols
+            }
         }
     };
 }
@@ -10,13 +10,142 @@ pub trait NumberTrait: Token {
     // Create the into f64 method (according to std::convert::From)
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Digit(u8);
 
 impl Digit {
     pub fn from_char(c: char, base: u8) -> Option<Self> {
         c.to_digit(base.into()).map(|digit| Digit(digit as u8))
     }
+
+    /// Returns the value of the digit (e.g. `15` for `F`).
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+/// The smallest and largest bases a number can be written in (`2#1011` up to `36#Z`).
+pub const BASES: std::ops::RangeInclusive<u8> = 2..=36;
+
+/// Splits a number written in a base other than 10 into its base and its digits.
+///
+/// Accepts the `0b1011`, `0o17` and `0xFF` prefixes, and the general `base#digits` form (`16#1F`).
+pub fn split_radix(literal: &str) -> Option<(u8, &str)> {
+    let prefixed = |prefix: [char; 2]| {
+        literal
+            .strip_prefix('0')
+            .and_then(|rest| rest.strip_prefix(prefix))
+    };
+    if let Some(digits) = prefixed(['b', 'B']) {
+        return Some((2, digits));
+    }
+    if let Some(digits) = prefixed(['o', 'O']) {
+        return Some((8, digits));
+    }
+    if let Some(digits) = prefixed(['x', 'X']) {
+        return Some((16, digits));
+    }
+    let (base, digits) = literal.split_once('#')?;
+    let base = base
+        .parse::<u8>()
+        .ok()
+        .filter(|base| BASES.contains(base))?;
+    Some((base, digits))
+}
+
+/// Native number types that can be built from a sequence of [`Digit`]s in some base.
+pub trait FromDigits: Sized {
+    /// Builds the number from its digits (most significant first), returning `None` on overflow
+    /// or if the type can't hold a number in that base.
+    fn from_digits(digits: &[Digit], base: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_from_digits {
+    ($($native_type:ty),+ $(,)?) => {
+        $(impl FromDigits for $native_type {
+            fn from_digits(digits: &[Digit], base: u8, negative: bool) -> Option<Self> {
+                let sign: $native_type = match negative {
+                    true => (0 as $native_type).checked_sub(1)?,
+                    false => 1,
+                };
+                digits.iter().try_fold(0 as $native_type, |value, digit| {
+                    value
+                        .checked_mul(base as $native_type)?
+                        .checked_add(sign * digit.value() as $native_type)
+                })
+            }
+        })+
+    };
+}
+
+impl_from_digits!(usize, isize);
+
+impl FromDigits for f64 {
+    /// Real numbers are only written in base 10.
+    fn from_digits(digits: &[Digit], base: u8, negative: bool) -> Option<Self> {
+        None
+    }
+}
+
+/// Native number types that can be written back in the base they were read in.
+trait ToDigits {
+    /// Writes the number in `base`, with the prefix of that base (`0xFF`, `3#12`).
+    fn to_digits(&self, base: u8) -> String;
+}
+
+macro_rules! impl_to_digits {
+    ($($native_type:ty),+ $(,)?) => {
+        $(impl ToDigits for $native_type {
+            fn to_digits(&self, base: u8) -> String {
+                let prefix = match base {
+                    10 => return self.to_string(),
+                    2 => "0b".to_string(),
+                    8 => "0o".to_string(),
+                    16 => "0x".to_string(),
+                    _ => format!("{base}#"),
+                };
+                let value = *self as i128;
+                let mut magnitude = value.unsigned_abs();
+                let mut digits = Vec::new();
+                loop {
+                    let digit = std::char::from_digit((magnitude % base as u128) as u32, base as u32);
+                    digits.push(digit.unwrap_or('?').to_ascii_uppercase());
+                    magnitude /= base as u128;
+                    if magnitude == 0 {
+                        break;
+                    }
+                }
+                let sign = if value < 0 { "-" } else { "" };
+                format!("{sign}{prefix}{}", digits.iter().rev().collect::<String>())
+            }
+        })+
+    };
+}
+
+impl_to_digits!(usize, isize);
+
+impl ToDigits for f64 {
+    /// Real numbers are only written in base 10.
+    fn to_digits(&self, _base: u8) -> String {
+        self.to_string()
+    }
+}
+
+/// Parses a number written with a base prefix (see [`split_radix`]), like `0xFF` or `-2#101`.
+///
+/// Returns the number and its base.
+fn parse_radix<N: FromDigits>(literal: &str) -> Option<(N, u8)> {
+    let (negative, literal) = match literal.strip_prefix('-') {
+        Some(literal) => (true, literal),
+        None => (false, literal),
+    };
+    let (base, digits) = split_radix(literal)?;
+    let digits = digits
+        .chars()
+        .map(|c| Digit::from_char(c, base))
+        .collect::<Option<Vec<Digit>>>()
+        .filter(|digits| !digits.is_empty())?;
+    N::from_digits(&digits, base, negative).map(|value| (value, base))
 }
 
 /// Macro to define numeric types and their associated traits.
@@ -28,9 +157,12 @@ impl Digit {
 /// - `$name`: The name of the struct representing a specific numeric type (e.g., `Natural`, `Integer`, `Real`).
 /// - `$native_type`: The native Rust type that the struct wraps (e.g., `usize`, `isize`, `f64`).
 ///
+/// Every numeric type keeps the base it was written in (see [`split_radix`]) to display it,
+/// but numbers are compared and hashed by value only (`0xF` equals `15`).
+///
 /// # Example
 ///
-/// ```rust
+/// ```ignore
 /// define_numeric_type!(Number; NumberTrait;
 ///     Natural(usize),
 ///     Integer(isize),
@@ -45,15 +177,44 @@ macro_rules! define_numeric_type {
         $(,)?
     ) => {
         $(
-            #[derive(Debug, Clone, PartialEq)]
-            pub struct $name { value: $native_type }
+            #[derive(Debug, Clone)]
+            pub struct $name { value: $native_type, base: u8 }
+
+            impl PartialEq for $name {
+                fn eq(&self, other: &Self) -> bool {
+                    self.value.bits() == other.value.bits()
+                }
+            }
+
+            impl Eq for $name {}
+
+            impl std::hash::Hash for $name {
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    self.value.bits().hash(state);
+                }
+            }
 
             impl $name {
                 /// Creates a new instance of the numeric type from the native Rust type.
                 /// # Parameters
                 /// - `value`: The native Rust type value to be wrapped.
                 pub fn from_n(value: $native_type) -> Self {
-                    Self { value }
+                    Self { value, base: 10 }
+                }
+
+                /// Creates a new instance of the numeric type, written in the given base.
+                pub fn from_n_in_base(value: $native_type, base: u8) -> Self {
+                    Self { value, base }
+                }
+
+                /// Returns the value of the number.
+                pub fn value(&self) -> $native_type {
+                    self.value
+                }
+
+                /// Returns the base the number was written in.
+                pub fn base(&self) -> u8 {
+                    self.base
                 }
             }
 
@@ -66,15 +227,50 @@ macro_rules! define_numeric_type {
                 /// # Returns
                 /// - `Option<Self>`: An instance of the numeric type if the parsing succeeds, `None` otherwise.
                 fn from_str<S: Into<String>>(string: S) -> Option<Self> {
-                    string.into().parse::<$native_type>().ok().map(|value| Self { value })
+                    let string = string.into();
+                    // * only plain numerals (`nan`, `inf` or `+1` aren't number tokens)
+                    let unsigned = string.strip_prefix('-').unwrap_or(&string);
+                    if !unsigned.starts_with(|c: char| c.is_ascii_digit()) {
+                        return None;
+                    }
+                    match parse_radix::<$native_type>(&string) {
+                        Some((value, base)) => Some(Self { value, base }),
+                        None => string.parse::<$native_type>().ok().map(Self::from_n),
+                    }
+                }
+
+                fn text(&self) -> String {
+                    self.value.to_digits(self.base)
                 }
             }
         )+
-        // Once all the numeric types have been defined, implement the trait for the token type...
-        crate::impl_token_trait!($token_type; $trait_name; $($name),+);
     };
 }
 
+/// Native number types that number tokens are compared and hashed by the bits of, so that
+/// a token holding a float is still [`Eq`] and [`Hash`] (`NaN` equals itself, `-0.0` isn't `0.0`).
+trait NumberBits: Copy {
+    fn bits(self) -> u64;
+}
+
+impl NumberBits for usize {
+    fn bits(self) -> u64 {
+        self as u64
+    }
+}
+
+impl NumberBits for isize {
+    fn bits(self) -> u64 {
+        self as u64
+    }
+}
+
+impl NumberBits for f64 {
+    fn bits(self) -> u64 {
+        self.to_bits()
+    }
+}
+
 // Use the macro to define Natural, Integer, and Real
 define_numeric_type!(Number; NumberTrait;
     Natural(usize),
@@ -82,59 +278,212 @@ define_numeric_type!(Number; NumberTrait;
     Real(f64),
 );
 
-// todo: Improve define_numeric_type! macro to handle Imaginary and Complex numbers
-// todo: Also modify the code above to make it able to use the Digit type to parse the numbers!
-// todo: Using the Digit type will allow the input of numbers in ANY BASE!
-// #[derive(Debug, Clone, PartialEq)]
-// pub struct Imaginary {
-//     value: f64,
-// }
-
-// impl Imaginary {
-//     pub fn from_n(value: f64) -> Self { Self { value } }
-// }
-
-// impl NumberTrait for Imaginary {}
-
-// impl Token for Imaginary {
-//     fn from_str<S: Into<String>>(string: S) -> Option<Self> {
-//         let string = string.into();
-//         if string.ends_with('i') {
-//             let real_part = &string[..string.len() - 1];
-//             real_part.parse::<f64>().ok().map(|value| Self { value })
-//         } else {
-//             None
-//         }
-//     }
-// }
-
-// #[derive(Debug, Clone, PartialEq)]
-// pub struct Complex {
-//     real: f64,
-//     imag: f64,
-// }
-
-// impl Complex {
-//     pub fn from_parts(real: f64, imag: f64) -> Self { Self { real, imag } }
-// }
-
-// impl NumberTrait for Complex {}
-
-// impl Token for Complex {
-//     fn from_str<S: Into<String>>(string: S) -> Option<Self> {
-//         let string = string.into();
-//         let parts: Vec<&str> = string.split(|c| c == '+' || c == '-').collect();
-//         if parts.len() == 2 {
-//             let real_part = parts[0];
-//             let imag_part = &parts[1][..parts[1].len() - 1]; // Remove the trailing 'i'
-
-//             if let (Ok(real), Ok(imag)) = (real_part.parse::<f64>(), imag_part.parse::<f64>()) {
-//                 let sign = if string.contains('-') { -1.0 } else { 1.0 };
-//                 return Some(Self { real, imag: sign * imag });
-//             }
-//         }
-//         None
-//     }
-// }
-
-// todo: Also
+/// An exact fraction written as `Integer '/' Natural` (e.g. `3/4`), kept in lowest terms.
+///
+/// The [`Lexer`](crate::lexer::Lexer) only reads `3/4` as a single rational when asked to
+/// (see [`Lexer::with_rationals`](crate::lexer::Lexer::with_rationals)), otherwise it's a division.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: isize,
+    denominator: usize,
+}
+
+impl Rational {
+    /// Creates the fraction `numerator / denominator` in lowest terms (`None` if the denominator is 0).
+    pub fn new(numerator: isize, denominator: usize) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let gcd = gcd(numerator.unsigned_abs(), denominator);
+        Some(Self {
+            numerator: numerator / gcd as isize,
+            denominator: denominator / gcd,
+        })
+    }
+
+    pub fn numerator(&self) -> isize {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> usize {
+        self.denominator
+    }
+
+    /// Returns the (approximate) value of the fraction.
+    pub fn value(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+/// Greatest common divisor (1 if both numbers are 0).
+fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a.max(1),
+        _ => gcd(b, a % b),
+    }
+}
+
+impl NumberTrait for Rational {}
+
+impl Token for Rational {
+    fn from_str<S: Into<String>>(string: S) -> Option<Self> {
+        let string = string.into();
+        let (numerator, denominator) = string.split_once('/')?;
+        let numerator = Integer::from_str(numerator).filter(|n| n.base() == 10)?;
+        let denominator = Natural::from_str(denominator).filter(|n| n.base() == 10)?;
+        Self::new(numerator.value(), denominator.value())
+    }
+
+    fn text(&self) -> String {
+        format!("{}/{}", self.numerator, self.denominator)
+    }
+}
+
+/// An imaginary number, written as a [`Real`] followed by `i` (e.g. `2i`, `1.5e3i`).
+#[derive(Debug, Clone)]
+pub struct Imaginary {
+    value: f64,
+}
+
+impl PartialEq for Imaginary {
+    fn eq(&self, other: &Self) -> bool {
+        self.value.bits() == other.value.bits()
+    }
+}
+
+impl Eq for Imaginary {}
+
+impl std::hash::Hash for Imaginary {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.bits().hash(state);
+    }
+}
+
+impl Imaginary {
+    pub fn from_n(value: f64) -> Self {
+        Self { value }
+    }
+
+    /// Returns the coefficient of `i`.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+impl NumberTrait for Imaginary {}
+
+impl Token for Imaginary {
+    fn from_str<S: Into<String>>(string: S) -> Option<Self> {
+        let string = string.into();
+        let coefficient = string.strip_suffix('i')?;
+        Real::from_str(coefficient).map(|n| Self::from_n(n.value()))
+    }
+
+    fn text(&self) -> String {
+        format!("{}i", self.value)
+    }
+}
+
+/// A complex number, written as `Real ('+' | '-') Imaginary` without spaces (e.g. `3+4i`).
+#[derive(Debug, Clone)]
+pub struct Complex {
+    real: f64,
+    imaginary: f64,
+}
+
+impl PartialEq for Complex {
+    fn eq(&self, other: &Self) -> bool {
+        (self.real.bits(), self.imaginary.bits()) == (other.real.bits(), other.imaginary.bits())
+    }
+}
+
+impl Eq for Complex {}
+
+impl std::hash::Hash for Complex {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (self.real.bits(), self.imaginary.bits()).hash(state);
+    }
+}
+
+impl Complex {
+    pub fn from_parts(real: f64, imaginary: f64) -> Self {
+        Self { real, imaginary }
+    }
+
+    pub fn real(&self) -> f64 {
+        self.real
+    }
+
+    pub fn imaginary(&self) -> f64 {
+        self.imaginary
+    }
+}
+
+impl NumberTrait for Complex {}
+
+impl Token for Complex {
+    fn from_str<S: Into<String>>(string: S) -> Option<Self> {
+        let string = string.into();
+        // * the sign between the parts (not the one of an exponent, like in `1e-3+2i`)
+        let split = string
+            .char_indices()
+            .skip(1)
+            .filter(|(i, c)| matches!(c, '+' | '-') && !string[..*i].ends_with(['e', 'E']))
+            .map(|(i, _)| i)
+            .last()?;
+        let real = Real::from_str(&string[..split])?;
+        let imaginary = Imaginary::from_str(&string[split + 1..])?;
+        let sign = if string[split..].starts_with('-') {
+            -1.0
+        } else {
+            1.0
+        };
+        Some(Self::from_parts(real.value(), sign * imaginary.value()))
+    }
+
+    fn text(&self) -> String {
+        format!("{}{:+}i", self.real, self.imaginary)
+    }
+}
+
+// * The number tower (`Natural ⊂ Integer ⊂ Rational ⊂ Real ⊂ Complex`): a number can always be
+// * promoted to a wider type (only a `Natural` too big for an `Integer` can't)
+
+impl TryFrom<Natural> for Integer {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(n: Natural) -> Result<Self, Self::Error> {
+        isize::try_from(n.value()).map(|value| Integer::from_n_in_base(value, n.base()))
+    }
+}
+
+impl From<Integer> for Rational {
+    fn from(n: Integer) -> Self {
+        Self {
+            numerator: n.value(),
+            denominator: 1,
+        }
+    }
+}
+
+impl From<Rational> for Real {
+    fn from(n: Rational) -> Self {
+        Real::from_n(n.value())
+    }
+}
+
+impl From<Real> for Complex {
+    fn from(n: Real) -> Self {
+        Complex::from_parts(n.value(), 0.0)
+    }
+}
+
+impl From<Imaginary> for Complex {
+    fn from(n: Imaginary) -> Self {
+        Complex::from_parts(0.0, n.value())
+    }
+}
+
+// Once all the numeric types have been defined, implement the trait for the token type...
+// * in the order of the number tower, so a number is read as the smallest type that holds it
+crate::impl_token_trait!(Number; NumberTrait; Natural, Integer, Rational, Real, Imaginary, Complex);
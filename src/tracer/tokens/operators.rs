@@ -2,13 +2,125 @@ use super::*;
 
 use crate::impl_enum_token;
 
-pub trait OperatorTrait: Token {
-    // * Add properties and methods for the operator type...
-    // todo: Add all the stuff below...
-    // todo: Add `precedence`, `associativity`, and `arity` properties for the operator type...
-    // todo: Add `is_unary`, `is_binary`, and `is_nary` methods for the operator type... (use `arity`)
-    // todo: Add `is_left_associative`, `is_right_associative`, and `is_non_associative` methods for the operator type... (use `associativity`)
+/// The associativity of a binary operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Associativity {
+    /// `a op b op c` is read as `(a op b) op c`.
+    Left,
+    /// `a op b op c` is read as `a op (b op c)`.
+    Right,
+    /// `a op b op c` isn't read as either grouping (comparisons are chained instead).
+    NonAssociative,
 }
+
+/// How an operator is written (between, before or after its operands) and how tight it binds.
+///
+/// Higher precedences bind tighter. An operator can be written in more than one way,
+/// like `-`, that is both binary (`a - b`) and prefix (`-a`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Notation {
+    /// Precedence and associativity as a binary operator.
+    pub infix: Option<(u8, Associativity)>,
+    /// Precedence as a prefix operator: its operand spans the operators that bind at least as tight.
+    pub prefix: Option<u8>,
+    /// Precedence as a postfix operator.
+    pub postfix: Option<u8>,
+}
+
+impl Notation {
+    /// Creates the notation of a token that isn't written as an operator.
+    pub const fn new() -> Self {
+        Self {
+            infix: None,
+            prefix: None,
+            postfix: None,
+        }
+    }
+
+    /// Makes the operator binary, with the given precedence and associativity.
+    pub const fn infix(mut self, precedence: u8, associativity: Associativity) -> Self {
+        self.infix = Some((precedence, associativity));
+        self
+    }
+
+    /// Makes the operator prefix, with the given precedence.
+    pub const fn prefix(mut self, precedence: u8) -> Self {
+        self.prefix = Some(precedence);
+        self
+    }
+
+    /// Makes the operator postfix, with the given precedence.
+    pub const fn postfix(mut self, precedence: u8) -> Self {
+        self.postfix = Some(precedence);
+        self
+    }
+}
+
+/// Declares the [`Notation`] of an operator. Implemented by `impl_operator_token!` from its table.
+pub trait OperatorNotation {
+    /// Returns the default notation of the operator (a [`Dialect`](crate::dialect::Dialect)
+    /// can override it, see [`Dialect::notation`](crate::dialect::Dialect::notation)).
+    fn notation(&self) -> Notation;
+}
+
+pub trait OperatorTrait: Token + OperatorNotation {
+    /// Returns the precedence of the operator (higher binds tighter).
+    ///
+    /// For an operator that can be both binary and unary (like `-`), that's the binary precedence.
+    fn precedence(&self) -> u8 {
+        let notation = self.notation();
+        let infix = notation.infix.map(|(precedence, _)| precedence);
+        infix
+            .or(notation.prefix)
+            .or(notation.postfix)
+            .unwrap_or_default()
+    }
+
+    /// Returns the associativity of the operator.
+    ///
+    /// Unary operators nest towards their operand: prefix ones are right associative (`¬¬A`),
+    /// postfix ones are left associative (`n!!`).
+    fn associativity(&self) -> Associativity {
+        let notation = self.notation();
+        match (notation.infix, notation.prefix) {
+            (Some((_, associativity)), _) => associativity,
+            (None, Some(_)) => Associativity::Right,
+            (None, None) => Associativity::Left,
+        }
+    }
+
+    /// Returns the number of operands the operator takes (2 if it can be binary).
+    fn arity(&self) -> usize {
+        match self.is_binary() {
+            true => 2,
+            false => 1,
+        }
+    }
+
+    /// Returns true if the operator can take a single operand (prefix or postfix).
+    fn is_unary(&self) -> bool {
+        let notation = self.notation();
+        notation.prefix.is_some() || notation.postfix.is_some()
+    }
+
+    /// Returns true if the operator can take two operands.
+    fn is_binary(&self) -> bool {
+        self.notation().infix.is_some()
+    }
+
+    fn is_left_associative(&self) -> bool {
+        self.associativity() == Associativity::Left
+    }
+
+    fn is_right_associative(&self) -> bool {
+        self.associativity() == Associativity::Right
+    }
+
+    fn is_non_associative(&self) -> bool {
+        self.associativity() == Associativity::NonAssociative
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Operator;
 
@@ -25,41 +137,43 @@ pub trait OperatorNegator: OperatorTrait {
 /// - `$name`: The name of the enum representing a specific operator type (e.g., `MathOp`).
 /// - `$negator`: An optional parameter representing the negator variant for the operator type.
 /// - `$variant`: The variants of the operator enum, mapping strings to enum variants.
+/// - `$fixity`: How each variant is written, as calls to the [`Notation`] builder
+///   (`infix(precedence, associativity)`, `prefix(precedence)` or `postfix(precedence)`).
 ///
 /// # Example
 ///
-/// ```rust
+/// ```ignore
 /// impl_operator_token!(Operator; OperatorTrait;
 ///     MathOp (Subtract;  // this will be the negator of the operator type
-///         Add => ("+"),
-///         Subtract => ("-"),
-///         Multiply => ("*"),
-///         Divide => ("/"),
-///         Modulo => ("%"),
-///         Power => ("^"),
-///         Root => ("√"),
-///         Factorial => ("!"),
+///         Add => ("+") infix(20, Left) prefix(22),
+///         Subtract => ("-") infix(20, Left) prefix(22),
+///         Multiply => ("*") infix(21, Left),
+///         Divide => ("/") infix(21, Left),
+///         Modulo => ("%") infix(21, Left),
+///         Power => ("^") infix(23, Right),
+///         Root => ("√") prefix(24),
+///         Factorial => ("!") postfix(25),
 ///     ),
 ///     LogicOp (Not;  // this will be the negator of the operator type
-///         And => ("&", "^", "∧", "+"),
-///         Or => ("|", "||", "*"),
-///         Not => ("!", "~", "¬"),
-///         XOr => ("^", "⊻", "⨁"),
-///         XNOr => ("⊙", "⊽"),
-///         NAnd => ("↑"),
-///         NOr => ("↓"),
+///         And => ("&", "∧", "*", "⋅") infix(5, Left),
+///         Or => ("|", "||", "∨", "+") infix(3, Left),
+///         Not => ("!", "~", "¬") prefix(6),
+///         XOr => ("^", "⊻", "⨁", "⊕") infix(4, Left),
+///         XNOr => ("⊙", "⊽") infix(4, Left),
+///         NAnd => ("↑") infix(5, Left),
+///         NOr => ("↓") infix(3, Left),
 ///     ),
 ///     RelationalOp (NotEqual;
-///         Equal => ("==", "="),
-///         NotEqual => ("!=", "≠"),
-///         LessThan => ("<"),
-///         LessThanOrEqual => ("<=", "≤"),
-///         GreaterThan => (">"),
-///         GreaterThanOrEqual => (">=", "≥"),
+///         Equal => ("==", "=") infix(8, NonAssociative),
+///         NotEqual => ("!=", "≠") infix(8, NonAssociative),
+///         LessThan => ("<") infix(8, NonAssociative),
+///         LessThanOrEqual => ("<=", "≤") infix(8, NonAssociative),
+///         GreaterThan => (">") infix(8, NonAssociative),
+///         GreaterThanOrEqual => (">=", "≥") infix(8, NonAssociative),
 ///     ),
 ///     SomeOtherOp (;  // this will not have a negator (don't implement OperatorNegator for it)
-///         SomeOp => ("some_op"),
-///         AnotherOp => ("another_op"),
+///         SomeOp => ("some_op") infix(1, Left),
+///         AnotherOp => ("another_op") prefix(1),
 ///    ),
 /// );
 /// ```
@@ -68,9 +182,9 @@ macro_rules! impl_operator_token {
         $($name:ident (
             $( $negator:ident )?;
             $(
-                $variant:ident => ($($str:expr),+)
-                $(,)?
-            )+
+                $variant:ident => ($($str:expr),+) $($fixity:ident($($arg:expr),+))+
+            ),+
+            $(,)?
         )),+
         $(,)?
     ) => {
@@ -83,6 +197,15 @@ macro_rules! impl_operator_token {
         );
 
         $(
+            impl OperatorNotation for $name {
+                fn notation(&self) -> Notation {
+                    use Associativity::*;
+                    match self {
+                        $($name::$variant => Notation::new()$(.$fixity($($arg),+))+,)+
+                    }
+                }
+            }
+
             $(
                 impl OperatorNegator for $name {
                     const NEGATOR: Self = $name::$negator;
@@ -92,32 +215,55 @@ macro_rules! impl_operator_token {
     };
 }
 
+// * precedences: logic operators 1 to 6, comparisons 8, bitwise operators 12 to 15, math operators 20 to 25
+
 impl_operator_token!(Operator; OperatorTrait;
     MathOp (Subtract;
-        Add => ("+"),
-        Subtract => ("-"),
-        Multiply => ("*"),
-        Divide => ("/"),
-        Modulo => ("%"),
-        Power => ("^"),
-        Root => ("√"),
-        Factorial => ("!"),
+        Add => ("+") infix(20, Left) prefix(22),
+        Subtract => ("-") infix(20, Left) prefix(22),
+        Multiply => ("*", "×", "\\times", "\\cdot") infix(21, Left),
+        Divide => ("/", "÷", "\\div") infix(21, Left),
+        Modulo => ("%", "mod", "\\bmod") infix(21, Left),
+        Power => ("^") infix(23, Right),
+        Root => ("√", "\\sqrt") prefix(24),
+        Factorial => ("!") postfix(25),
     ),
+    // * word symbols (`and`, `mod`) are matched case-insensitively, and only as whole words
+    // * symbols follow the grammar (`*` is AND, `+` is OR, `^` is XOR),
+    // * see [`Dialect`](crate::dialect::Dialect) for the notations that read them differently
+    // * the first spelling of each style is the one rendered (see `Style`),
+    // * so it must mean the same in every lexer and dialect (`^` doesn't, so XOR is `xor` in ASCII)
+    // * `=` is an equivalence (`A & 1 = A`) only where comparisons aren't read (see `Lexer`)
     LogicOp (Not;
-        And => ("&", "^", "∧", "+"),
-        Or => ("|", "||", "*"),
-        Not => ("!", "~", "¬"),
-        XOr => ("^", "⊻", "⨁"),
-        XNOr => ("⊙", "⊽"),
-        NAnd => ("↑"),
-        NOr => ("↓"),
+        And => ("&", "∧", "*", "⋅", "and", "\\land", "\\wedge", "\\cdot") infix(5, Left),
+        Or => ("|", "||", "∨", "+", "or", "\\lor", "\\vee") infix(3, Left),
+        Not => ("¬", "!", "~", "not", "\\neg", "\\lnot") prefix(6),
+        XOr => ("⊕", "xor", "^", "⊻", "⨁", "\\oplus", "\\veebar") infix(4, Left),
+        XNOr => ("⊙", "⊽", "xnor", "\\odot") infix(4, Left),
+        NAnd => ("↑", "nand", "\\uparrow") infix(5, Left),
+        NOr => ("↓", "nor", "\\downarrow") infix(3, Left),
+        Implies => ("→", "⇒", "->", "=>", "implies", "\\implies", "\\Rightarrow", "\\rightarrow", "\\to") infix(2, Right),
+        ConverseImplies => ("←", "⇐", "<-", "\\impliedby", "\\Leftarrow", "\\leftarrow", "\\gets") infix(2, Left),
+        Iff => ("↔", "⇔", "<->", "<=>", "iff", "\\iff", "\\Leftrightarrow", "\\leftrightarrow", "=") infix(1, Left),
+        NotImplies => ("↛", "⇏", "-/>", "\\nrightarrow", "\\nRightarrow") infix(2, Right),
+    ),
+    // * bitwise operators over the integers, read instead of the logic ones by the `BitLexer`
+    BitOp (Not;
+        And => ("&", "bitand") infix(14, Left),
+        Or => ("|", "bitor") infix(12, Left),
+        XOr => ("^", "bitxor") infix(13, Left),
+        Not => ("~", "bitnot") prefix(22),
+        ShiftLeft => ("<<", "shl") infix(15, Left),
+        ShiftRight => (">>", "shr") infix(15, Left),
+        RotateLeft => ("<<<", "rotl") infix(15, Left),
+        RotateRight => (">>>", "rotr") infix(15, Left),
     ),
     RelationalOp (;  // ^ without implementing OperatorNegator trait
-        Equal => ("==", "="),
-        NotEqual => ("!=", "≠"),
-        LessThan => ("<"),
-        LessThanOrEqual => ("<=", "≤"),
-        GreaterThan => (">"),
-        GreaterThanOrEqual => (">=", "≥"),
+        Equal => ("==", "=") infix(8, NonAssociative),
+        NotEqual => ("!=", "≠", "\\neq", "\\ne") infix(8, NonAssociative),
+        LessThan => ("<", "\\lt") infix(8, NonAssociative),
+        LessThanOrEqual => ("<=", "≤", "\\leq", "\\le") infix(8, NonAssociative),
+        GreaterThan => (">", "\\gt") infix(8, NonAssociative),
+        GreaterThanOrEqual => (">=", "≥", "\\geq", "\\ge") infix(8, NonAssociative),
     ),
 );
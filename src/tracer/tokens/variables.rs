@@ -22,7 +22,7 @@ pub trait VariableTrait: Token {
 ///
 /// # Example
 ///
-/// ```rust
+/// ```ignore
 /// define_constants!(
 ///     Math (
 ///         Tau => "τ",
@@ -124,67 +124,72 @@ define_constants!(Variable; VariableTrait;
         Z => ("Z"),
     ),
     GreekAlpha (
-        Alpha => ("α"),
-        Beta => ("β"),
-        Gamma => ("γ"),
-        Delta => ("δ"),
-        Epsilon => ("ε"),
-        Zeta => ("ζ"),
-        Eta => ("η"),
-        Theta => ("θ"),
-        Iota => ("ι"),
-        Kappa => ("κ"),
-        Lambda => ("λ"),
-        Mu => ("μ"),
-        Nu => ("ν"),
-        Xi => ("ξ"),
+        Alpha => ("α", "\\alpha"),
+        Beta => ("β", "\\beta"),
+        Gamma => ("γ", "\\gamma"),
+        Delta => ("δ", "\\delta"),
+        Epsilon => ("ε", "\\epsilon", "\\varepsilon"),
+        Zeta => ("ζ", "\\zeta"),
+        Eta => ("η", "\\eta"),
+        Theta => ("θ", "\\theta", "\\vartheta"),
+        Iota => ("ι", "\\iota"),
+        Kappa => ("κ", "\\kappa"),
+        Lambda => ("λ", "\\lambda"),
+        Mu => ("μ", "\\mu"),
+        Nu => ("ν", "\\nu"),
+        Xi => ("ξ", "\\xi"),
         Omicron => ("ο"),
-        Pi => ("π"),
-        Rho => ("ρ"),
-        Sigma => ("σ"),
-        Tau => ("τ"),
-        Upsilon => ("υ"),
-        Phi => ("φ"),
-        Chi => ("χ"),
-        Psi => ("ψ"),
-        Omega => ("ω"),
+        Pi => ("π", "\\pi", "\\varpi"),
+        Rho => ("ρ", "\\rho", "\\varrho"),
+        Sigma => ("σ", "\\sigma", "\\varsigma"),
+        Tau => ("τ", "\\tau"),
+        Upsilon => ("υ", "\\upsilon"),
+        Phi => ("φ", "\\phi", "\\varphi"),
+        Chi => ("χ", "\\chi"),
+        Psi => ("ψ", "\\psi"),
+        Omega => ("ω", "\\omega"),
     ),
     GreekUpperAlpha (
         Alpha => ("Α"),
         Beta => ("Β"),
-        Gamma => ("Γ"),
-        Delta => ("Δ"),
+        Gamma => ("Γ", "\\Gamma"),
+        Delta => ("Δ", "\\Delta"),
         Epsilon => ("Ε"),
         Zeta => ("Ζ"),
         Eta => ("Η"),
-        Theta => ("Θ"),
+        Theta => ("Θ", "\\Theta"),
         Iota => ("Ι"),
         Kappa => ("Κ"),
-        Lambda => ("Λ"),
+        Lambda => ("Λ", "\\Lambda"),
         Mu => ("Μ"),
         Nu => ("Ν"),
-        Xi => ("Ξ"),
+        Xi => ("Ξ", "\\Xi"),
         Omicron => ("Ο"),
-        Pi => ("Π"),
+        Pi => ("Π", "\\Pi"),
         Rho => ("Ρ"),
-        Sigma => ("Σ"),
+        Sigma => ("Σ", "\\Sigma"),
         Tau => ("Τ"),
-        Upsilon => ("Υ"),
-        Phi => ("Φ"),
+        Upsilon => ("Υ", "\\Upsilon"),
+        Phi => ("Φ", "\\Phi"),
         Chi => ("Χ"),
-        Psi => ("Ψ"),
-        Omega => ("Ω"),
+        Psi => ("Ψ", "\\Psi"),
+        Omega => ("Ω", "\\Omega"),
     ),
 
     // * CONSTANTS
     MathConst (
         Tau => ("τ", "\\tau"),
         Pi => ("π", "\\pi"),
-        Phi => ("φ"),
-        EulerGamma => ("γ"),
-        Infinity => ("∞"),
+        Phi => ("φ", "\\phi"),
+        EulerGamma => ("γ", "\\gamma"),
+        Infinity => ("∞", "\\infty"),
         E => ("e"),
     ),
+    // * `1` and `0` are only read as booleans by recognizers that list BoolConst before the numbers
+    BoolConst (
+        True => ("⊤", "true", "1", "\\top"),
+        False => ("⊥", "false", "0", "\\bot"),
+    ),
     PhysicConst (
         SpeedOfLight => ("\\c"),
         PlanckConstant => ("\\h"),
@@ -195,3 +200,125 @@ define_constants!(Variable; VariableTrait;
     ),
 
 );
+
+impl MathConst {
+    /// Returns the numeric value of the constant.
+    pub fn value(&self) -> f64 {
+        match self {
+            MathConst::Tau => std::f64::consts::TAU,
+            MathConst::Pi => std::f64::consts::PI,
+            MathConst::Phi => 1.618_033_988_749_895,
+            MathConst::EulerGamma => 0.577_215_664_901_532_9,
+            MathConst::Infinity => f64::INFINITY,
+            MathConst::E => std::f64::consts::E,
+        }
+    }
+}
+
+impl PhysicConst {
+    /// Returns the numeric value of the constant, in SI units.
+    pub fn value(&self) -> f64 {
+        match self {
+            PhysicConst::SpeedOfLight => 299_792_458.0,
+            PhysicConst::PlanckConstant => 6.626_070_15e-34,
+            PhysicConst::Gravitational => 6.674_30e-11,
+            PhysicConst::MuonMass => 1.883_531_627e-28,
+            PhysicConst::ElectronMass => 9.109_383_701_5e-31,
+            PhysicConst::ProtonMass => 1.672_621_923_69e-27,
+        }
+    }
+}
+
+impl BoolConst {
+    /// Reads the letters `T` and `F` as true and false.
+    ///
+    /// They aren't part of the symbols of the token because they are usually variables
+    /// (see [`Lexer::with_bool_letters`](crate::lexer::Lexer::with_bool_letters)).
+    pub fn from_letter(letter: &str) -> Option<Self> {
+        match letter {
+            "T" => Some(BoolConst::True),
+            "F" => Some(BoolConst::False),
+            _ => None,
+        }
+    }
+}
+
+/// A variable written as a name instead of a single symbol.
+///
+/// Follows the `Variable ::= Letter ('_' Natural)?` rule of the grammar (`x_1`, `A_12`, `θ_0`),
+/// and also accepts longer names made of letters, digits and underscores (`enable`, `carry_in`).
+/// The letters can be from any alphabet (Latin, Greek, ...), but a bare constant symbol
+/// (like `π` or `∞`) is never an identifier: it's read as a [`MathConst`] instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Identifier {
+    name: String,
+    subscript: Option<String>,
+}
+
+impl Identifier {
+    /// Returns the name of the identifier, without its subscript.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the numeric subscript of the identifier (the `1` in `x_1`), if any.
+    ///
+    /// It's kept as it's written, so `x_01` and `x_1` are different identifiers.
+    pub fn subscript(&self) -> Option<&str> {
+        self.subscript.as_deref()
+    }
+
+    /// Returns the identifier as it's written (e.g. `x_1`).
+    pub fn symbol(&self) -> String {
+        match &self.subscript {
+            Some(subscript) => format!("{}_{}", self.name, subscript),
+            None => self.name.clone(),
+        }
+    }
+}
+
+impl VariableTrait for Identifier {}
+
+impl Token for Identifier {
+    fn from_str<S: Into<String>>(string: S) -> Option<Self> {
+        let string = string.into();
+        let mut chars = string.chars();
+        if !chars.next().is_some_and(char::is_alphabetic)
+            || !chars.all(|c| c.is_alphanumeric() || c == '_')
+            || string.ends_with('_')
+            || (!string.is_ascii() && MathConst::from_str(string.as_str()).is_some())
+        {
+            return None;
+        }
+
+        // * a trailing `_Natural` is the subscript (`x_1`), any other `_` is part of the name (`carry_in`)
+        let (name, subscript) = match string.rsplit_once('_') {
+            Some((name, digits)) if digits.chars().all(|c| c.is_ascii_digit()) => {
+                (name.to_string(), Some(digits.to_string()))
+            }
+            _ => (string, None),
+        };
+        Some(Self { name, subscript })
+    }
+
+    fn text(&self) -> String {
+        self.symbol()
+    }
+}
+
+/// Returns the symbol a variable is looked up by, if the token is a variable.
+pub(crate) fn variable_name(token: &dyn Token) -> Option<String> {
+    if let Some(var) = token.downcast_ref::<Alphabet>() {
+        return Some(var.symbol().to_string());
+    }
+    if let Some(var) = token.downcast_ref::<AlphaUpper>() {
+        return Some(var.symbol().to_string());
+    }
+    if let Some(var) = token.downcast_ref::<GreekAlpha>() {
+        return Some(var.symbol().to_string());
+    }
+    if let Some(var) = token.downcast_ref::<GreekUpperAlpha>() {
+        return Some(var.symbol().to_string());
+    }
+    token.downcast_ref::<Identifier>().map(Identifier::symbol)
+}
@@ -0,0 +1,51 @@
+//! Checks that lexing frees everything it allocates.
+//!
+//! It lives in its own test binary so that its counting allocator doesn't slow down the other tests.
+
+use logic_tracer::lexer::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Global allocator that keeps track of the bytes currently allocated by each thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATED: Cell<isize> = const { Cell::new(0) };
+}
+
+fn track(bytes: isize) {
+    let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + bytes));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        track(layout.size() as isize);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        track(-(layout.size() as isize));
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_lexer_does_not_leak() {
+    let allocated = || ALLOCATED.with(|allocated| allocated.get());
+    let sources = ["A & B | !C", "25.1 * 42 - 13", "x $ y"];
+
+    // * the symbol tables are built once, on first use, and kept for the whole process
+    for src in sources {
+        let _ = Lexer::<CompleteLexer>::new(src).tokenize();
+    }
+
+    let before = allocated();
+    for i in 0..10_000 {
+        let src = sources[i % sources.len()];
+        let _ = Lexer::<CompleteLexer>::new(src).tokenize();
+    }
+    assert_eq!(allocated(), before); // every allocation made while lexing was freed
+}